@@ -1,7 +1,16 @@
 use crate::tesc::*;
 use crate::time::ClockTime;
 use crate::time::Date;
-use crate::{config::Config, store::Store, task::Task, time::Duration};
+use crate::{
+    config::Config,
+    deps::Graph,
+    event::Event,
+    store::Store,
+    task::{SubTask, Task},
+    time::Duration,
+    time::Span,
+    todo::Todo,
+};
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
@@ -21,16 +30,69 @@ pub struct Args {
     /// - "<x>y" x number of years after start
     #[clap(long, verbatim_doc_comment)]
     until: Option<String>,
+
+    /// hide tasks which are blocked by other open tasks instead of marking them
+    #[clap(long)]
+    hide_blocked: bool,
+
+    /// also show tasks which are deferred to a later date
+    #[clap(long)]
+    include_deferred: bool,
+
+    /// only show tasks assigned to `me` as configured in the config
+    #[clap(long, conflicts_with_all = ["assignee", "unassigned"])]
+    mine: bool,
+
+    /// only show tasks assigned to the given person
+    #[clap(long, conflicts_with = "unassigned")]
+    assignee: Option<String>,
+
+    /// only show tasks which are not assigned to anyone
+    #[clap(long)]
+    unassigned: bool,
 }
 
 struct Agenda<'a> {
-    overdue: Vec<&'a Task>,
+    overdue: Vec<Item<'a>>,
     entries: Vec<Entry<'a>>,
 }
 
 struct Entry<'a> {
     stamp: Date,
-    tasks: Vec<&'a Task>,
+    items: Vec<Item<'a>>,
+}
+
+#[derive(Clone, Copy)]
+enum Item<'a> {
+    Task {
+        task: &'a Task,
+        blocked: bool,
+    },
+    SubTask {
+        task: &'a Task,
+        subtask: &'a SubTask,
+    },
+    Event(&'a Event),
+    Todo(&'a Todo),
+}
+
+impl Item<'_> {
+    fn prefix(&self) -> &str {
+        match self {
+            Item::Task { task, .. } | Item::SubTask { task, .. } => &task.prefix,
+            Item::Event(event) => &event.prefix,
+            Item::Todo(todo) => todo_prefix(todo),
+        }
+    }
+
+    fn print(&self, prefix_pad: usize, subtask: bool) {
+        match self {
+            Item::Task { task, blocked } => task_print(task, *blocked, prefix_pad, subtask),
+            Item::SubTask { task, subtask } => subtask_print(task, subtask, prefix_pad),
+            Item::Event(event) => event_print(event, prefix_pad),
+            Item::Todo(todo) => todo_print(todo, prefix_pad),
+        }
+    }
 }
 
 fn clock_range_format(start: Option<ClockTime>, end: Option<ClockTime>) -> String {
@@ -42,7 +104,7 @@ fn clock_range_format(start: Option<ClockTime>, end: Option<ClockTime>) -> Strin
     }
 }
 
-fn task_print(task: &Task, prefix_pad: usize, subtask: bool) {
+fn task_print(task: &Task, blocked: bool, prefix_pad: usize, subtask: bool) {
     print!(
         "  {}{}{}{} ",
         magenta(),
@@ -66,16 +128,20 @@ fn task_print(task: &Task, prefix_pad: usize, subtask: bool) {
         print!(" [{}/{}]", is_done, task.subtasks.len());
     }
 
-    if task.scheduled.is_some() {
-        if let Some(span) = task.deadline {
-            print!(
-                " ({}{}D{} {})",
-                red(),
-                bold(),
-                reset(),
-                span.date.to_pretty_string().trim()
-            );
-        }
+    if task.scheduled.is_some()
+        && let Some(span) = task.deadline
+    {
+        print!(
+            " ({}{}D{} {})",
+            red(),
+            bold(),
+            reset(),
+            span.date.to_pretty_string().trim()
+        );
+    }
+
+    if blocked {
+        print!(" {}(blocked){}", yellow(), reset());
     }
 
     println!();
@@ -87,6 +153,72 @@ fn task_print(task: &Task, prefix_pad: usize, subtask: bool) {
     }
 }
 
+fn subtask_print(task: &Task, subtask: &SubTask, prefix_pad: usize) {
+    print!(
+        "  {}{}{}{} ",
+        magenta(),
+        task.prefix,
+        reset(),
+        " ".repeat(prefix_pad - task.prefix.len())
+    );
+
+    if let Some(span) = subtask.date {
+        print!("{}{}-{} ", green(), bold(), reset());
+        print!("{} ", clock_range_format(span.start, span.end));
+    }
+
+    println!("{}: {}", task.title, subtask.title);
+}
+
+fn event_print(event: &Event, prefix_pad: usize) {
+    print!(
+        "  {}{}{}{} ",
+        magenta(),
+        event.prefix,
+        reset(),
+        " ".repeat(prefix_pad - event.prefix.len())
+    );
+
+    if let Some(span) = event.at {
+        print!("{}{}E{} ", cyan(), bold(), reset());
+        print!("{} ", clock_range_format(span.start, span.end));
+    }
+
+    println!("{}", event.title);
+}
+
+/// todos have no prefix, therefore they are listed under the name of their file
+fn todo_prefix(todo: &Todo) -> &str {
+    todo.path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+}
+
+fn todo_print(todo: &Todo, prefix_pad: usize) {
+    let prefix = todo_prefix(todo);
+    print!(
+        "  {}{}{}{} ",
+        magenta(),
+        prefix,
+        reset(),
+        " ".repeat(prefix_pad - prefix.len())
+    );
+
+    print!("{}{}T{} ", yellow(), bold(), reset());
+    print!("{} ", clock_range_format(None, None));
+    print!("{}: {}", todo.kind, todo.title);
+
+    if let Some(owner) = todo.owner.as_deref() {
+        print!(" ({})", owner);
+    }
+    if let Some(issue) = todo.issue.as_deref() {
+        print!(" [{}]", issue);
+    }
+
+    println!();
+}
+
 fn parse_until(until: &str, start: Date) -> Date {
     if let Ok(until) = until.parse() {
         return until;
@@ -97,7 +229,30 @@ fn parse_until(until: &str, start: Date) -> Date {
         .expect("could not evaluate --until flag")
 }
 
-pub fn run(_: &Config, store: &Store, args: &Args) {
+pub fn run(config: &Config, store: &Store, args: &Args) {
+    let graph = Graph::new(&store.tasks);
+
+    let assignee = if args.mine {
+        let me = config
+            .me
+            .as_deref()
+            .expect("--mine requires `me` to be set in the config");
+        Some(me)
+    } else {
+        args.assignee.as_deref()
+    };
+    let assignee_filter = |task: &Task| match assignee {
+        Some(assignee) => task.is_assigned_to(assignee),
+        None if args.unassigned => task.assignees.is_empty(),
+        None => true,
+    };
+
+    let owner_filter = |todo: &Todo| match assignee {
+        Some(assignee) => todo.is_owned_by(assignee),
+        None if args.unassigned => todo.owner.is_none(),
+        None => true,
+    };
+
     let get_stamp = |task: &Task| match (task.scheduled, task.deadline) {
         (Some(stamp), _) => Some(stamp),
         (_, Some(stamp)) => Some(stamp),
@@ -105,72 +260,108 @@ pub fn run(_: &Config, store: &Store, args: &Args) {
     };
 
     let prefix_filter = args.prefix.as_deref().unwrap_or("");
-    let prefix_filter = |task: &Task| task.prefix.starts_with(prefix_filter);
+    let prefix_filter = |prefix: &str| prefix.starts_with(prefix_filter);
 
     let mut start = Date::today();
 
     let until = args.until.as_deref().unwrap_or("7d");
     let end = parse_until(until, start);
 
-    let mut tasks = store
+    let open_tasks = store
         .tasks
         .iter()
-        .filter(|task| task.state.is_open())
-        .map(|task| (get_stamp(task), task))
-        .filter(|(stamp, _)| stamp.is_some())
-        .map(|(stamp, task)| (stamp.unwrap(), task))
-        .filter(|(stamp, _)| stamp.date < end)
-        .filter(|(_, task)| prefix_filter(task))
+        .enumerate()
+        .filter(|(_, task)| task.state.is_open())
+        .filter(|(_, task)| args.include_deferred || !task.is_deferred(start))
+        .filter(|(_, task)| assignee_filter(task))
+        .map(|(i, task)| (graph.is_blocked(i), task))
+        .filter(|(blocked, _)| !(args.hide_blocked && *blocked))
+        .filter(|(_, task)| prefix_filter(&task.prefix))
         .collect::<Vec<_>>();
 
-    tasks.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let tasks = open_tasks
+        .iter()
+        .filter_map(|&(blocked, task)| Some((get_stamp(task)?, Item::Task { task, blocked })));
+
+    // dated subtasks are listed on their own day so they do not hide behind their parent
+    let subtasks = open_tasks.iter().flat_map(|&(_, task)| {
+        task.subtasks
+            .iter()
+            .filter(|subtask| subtask.is_not_done())
+            .filter_map(move |subtask| Some((subtask.date?, Item::SubTask { task, subtask })))
+    });
+
+    // events are never overdue, therefore events before today are dropped
+    let events = store
+        .events
+        .iter()
+        .filter(|event| prefix_filter(&event.prefix))
+        .filter_map(|event| Some((event.at?, Item::Event(event))))
+        .filter(|(stamp, _)| stamp.date >= start);
+
+    // todos do not have a prefix, therefore they are only shown when no prefix is given
+    let todos = store
+        .todos
+        .iter()
+        .filter(|_| args.prefix.is_none())
+        .filter(|todo| owner_filter(todo))
+        .filter_map(|todo| Some((Span::new(todo.due?, None, None), Item::Todo(todo))));
+
+    let mut items = tasks
+        .chain(subtasks)
+        .chain(events)
+        .chain(todos)
+        .filter(|(stamp, _)| stamp.date < end)
+        .collect::<Vec<(Span, Item)>>();
+
+    items.sort_by_key(|(a, _)| *a);
 
-    let prefix_pad = tasks
+    let prefix_pad = items
         .iter()
-        .map(|(_, task)| task.prefix.len())
+        .map(|(_, item)| item.prefix().len())
         .max()
         .unwrap_or_default();
 
     let mut agenda = Agenda {
-        overdue: Vec::with_capacity(tasks.len()),
+        overdue: Vec::with_capacity(items.len()),
         entries: Vec::with_capacity(8),
     };
 
-    let mut tasks = &tasks[..];
+    let mut items = &items[..];
 
     let today = Date::today();
-    while !tasks.is_empty() && tasks[0].0.date < today {
-        agenda.overdue.push(tasks[0].1);
-        tasks = &tasks[1..];
+    while !items.is_empty() && items[0].0.date < today {
+        agenda.overdue.push(items[0].1);
+        items = &items[1..];
     }
 
-    while !tasks.is_empty() && tasks[0].0.date < start {
-        tasks = &tasks[1..];
+    while !items.is_empty() && items[0].0.date < start {
+        items = &items[1..];
     }
 
     while start < end {
         let mut entry = Entry {
             stamp: start,
-            tasks: Vec::with_capacity(tasks.len()),
+            items: Vec::with_capacity(items.len()),
         };
 
         start = start.add_duration(Duration::Day(1)).unwrap();
 
-        while !tasks.is_empty() && tasks[0].0.date < start {
-            entry.tasks.push(tasks[0].1);
-            tasks = &tasks[1..];
+        while !items.is_empty() && items[0].0.date < start {
+            entry.items.push(items[0].1);
+            items = &items[1..];
         }
         agenda.entries.push(entry);
     }
 
     println!("{}{}Overdue{}", red(), bold(), reset());
-    for task in agenda.overdue.iter() {
-        task_print(task, prefix_pad, args.subtask);
+    for item in agenda.overdue.iter() {
+        item.print(prefix_pad, args.subtask);
     }
     for entry in agenda.entries.iter() {
         println!("{}{}{}", bold(), entry.stamp.to_pretty_string(), reset());
-        for task in entry.tasks.iter() {
-            task_print(task, prefix_pad, args.subtask);
+        for item in entry.items.iter() {
+            item.print(prefix_pad, args.subtask);
         }
     }
 }
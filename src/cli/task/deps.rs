@@ -0,0 +1,69 @@
+use crate::{config::Config, deps::Graph, store::Store, tesc::*};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// export the dependency graph in the Graphviz DOT format
+    #[clap(long)]
+    dot: bool,
+}
+
+/// prints every task which is blocked by other tasks together with its blockers, followed by
+/// dependency cycles, unresolved references and problems with relative dates
+pub fn run(_: &Config, store: &Store, args: &Args) {
+    let graph = Graph::new(&store.tasks);
+
+    if args.dot {
+        print!("{}", graph.to_dot());
+        return;
+    }
+
+    for (i, task) in store.tasks.iter().enumerate() {
+        if graph.blockers[i].is_empty() {
+            continue;
+        }
+
+        let color = if graph.is_blocked(i) {
+            yellow()
+        } else {
+            green()
+        };
+        println!(
+            "{}{}{} {}{}",
+            color,
+            bold(),
+            task.state,
+            task.heading_path(),
+            reset()
+        );
+
+        for &j in graph.blockers[i].iter() {
+            let blocker = &store.tasks[j];
+            println!("  <- {} {}", blocker.state, blocker.heading_path());
+        }
+    }
+
+    for cycle in graph.cycles() {
+        let tasks = cycle
+            .iter()
+            .map(|&i| store.tasks[i].heading_path())
+            .collect::<Vec<_>>();
+        println!("{}{}cycle:{} {}", red(), bold(), reset(), tasks.join(", "));
+    }
+
+    for (i, reference) in graph.unresolved.iter() {
+        let task = &store.tasks[*i];
+        println!(
+            "{}{}unresolved:{} {:?} in {} ({})",
+            red(),
+            bold(),
+            reset(),
+            reference,
+            task.heading_path(),
+            task.location()
+        );
+    }
+
+    for diagnostic in store.diagnostics.iter() {
+        println!("{}{}diagnostic:{} {}", red(), bold(), reset(), diagnostic);
+    }
+}
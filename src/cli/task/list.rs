@@ -1,6 +1,6 @@
 use fast_glob::glob_match;
 
-use crate::{config::Config, store::Store};
+use crate::{config::Config, store::Store, time::Date};
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
@@ -12,12 +12,21 @@ pub struct Args {
 
     #[clap(long)]
     glob: Option<String>,
+
+    /// also list tasks which are deferred to a later date
+    #[clap(long)]
+    include_deferred: bool,
 }
 
 /// prints the title of every task to stdout
 pub fn run(_: &Config, store: &Store, args: &Args) {
     let mut tasks = store.tasks.iter().collect::<Vec<_>>();
 
+    if !args.include_deferred {
+        let today = Date::today();
+        tasks.retain(|task| !task.is_deferred(today));
+    }
+
     if let Some(prefix) = &args.prefix {
         tasks.retain(|task| task.prefix.starts_with(prefix));
     }
@@ -28,7 +37,7 @@ pub fn run(_: &Config, store: &Store, args: &Args) {
 
     for task in tasks.iter() {
         if args.path {
-            print!("{}:{} ", task.path.to_str().unwrap(), task.line_number);
+            print!("{} ", task.location());
         }
         print!("TASK: {}: {}", task.prefix, task.title);
         println!();
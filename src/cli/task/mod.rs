@@ -1,6 +1,9 @@
-use crate::{config::Config, store::Store};
+use crate::{config::Config, store::Store, task::Task};
 
+mod deps;
 mod list;
+mod open_sources;
+mod show;
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
@@ -12,10 +15,41 @@ pub struct Args {
 enum Command {
     /// lists every task
     List(list::Args),
+
+    /// prints the dependency graph between tasks
+    Deps(deps::Args),
+
+    /// prints every detail of a task
+    Show(show::Args),
+
+    /// opens the sources of a task
+    OpenSources(open_sources::Args),
 }
 
 pub fn run(config: &Config, store: &Store, args: &Args) {
     match &args.command {
         Command::List(args) => list::run(config, store, args),
+        Command::Deps(args) => deps::run(config, store, args),
+        Command::Show(args) => show::run(config, store, args),
+        Command::OpenSources(args) => open_sources::run(config, store, args),
+    }
+}
+
+/// returns every task matching `selector`, which is either the id, the heading path or
+/// `path:line` (`path#cell:line` in notebooks) of a task
+///
+/// # Panics
+/// if no task matches `selector` the function panics with a meaningful error message
+fn select<'a>(store: &'a Store, selector: &str) -> Vec<&'a Task> {
+    let tasks = store
+        .tasks
+        .iter()
+        .filter(|task| task.matches(selector) || task.location() == selector)
+        .collect::<Vec<_>>();
+
+    if tasks.is_empty() {
+        panic!("no task matches: {:?}", selector);
     }
+
+    tasks
 }
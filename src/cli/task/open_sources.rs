@@ -0,0 +1,44 @@
+use crate::{config::Config, store::Store};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// id, heading path (`prefix/title`) or `path:line` of the task
+    selector: String,
+}
+
+/// opens every existing source of the selected tasks with `config.open_command`
+///
+/// # Panics
+/// if `config.open_command` is empty the function panics with a meaningful error message
+pub fn run(config: &Config, store: &Store, args: &Args) {
+    let mut command = config.open_command.split_whitespace();
+    let program = command
+        .next()
+        .expect("open_command in the config must not be empty");
+    let command_args = command.collect::<Vec<_>>();
+
+    for task in super::select(store, &args.selector) {
+        for source in task.sources.iter() {
+            if !source.exists() {
+                eprintln!("skipping missing source: {}", source.to_str().unwrap());
+                continue;
+            }
+
+            let status = std::process::Command::new(program)
+                .args(command_args.iter())
+                .arg(source)
+                .status();
+
+            match status {
+                Ok(status) if status.success() => (),
+                Ok(status) => eprintln!(
+                    "could not open: {} because {} exited with {}",
+                    source.to_str().unwrap(),
+                    program,
+                    status
+                ),
+                Err(err) => panic!("could not run {:?} because: {:?}", program, err),
+            }
+        }
+    }
+}
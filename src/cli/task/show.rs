@@ -0,0 +1,76 @@
+use crate::{config::Config, store::Store, task::Task, tesc::*};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// id, heading path (`prefix/title`) or `path:line` of the task
+    selector: String,
+}
+
+fn task_print(task: &Task) {
+    println!(
+        "{}{} {}{}",
+        bold(),
+        task.state,
+        task.heading_path(),
+        reset()
+    );
+    println!("{}{}{}", dim(), task.location(), reset());
+
+    if let Some(span) = task.scheduled {
+        println!("> SCHEDULED: {}", span);
+    }
+    if let Some(span) = task.deadline {
+        println!("> DEADLINE: {}", span);
+    }
+    if let Some(date) = task.defer {
+        println!("> DEFER: {}", date);
+    }
+    if let Some(id) = &task.id {
+        println!("> ID: {}", id);
+    }
+    if !task.assignees.is_empty() {
+        println!("> ASSIGNEE: {}", task.assignees.join(", "));
+    }
+    if !task.tags.is_empty() {
+        println!("> TAGS: {}", task.tags.join(", "));
+    }
+    if let Some(span) = task.closed {
+        println!("> CLOSED: {}", span);
+    }
+    if !task.blocked_by.is_empty() {
+        println!("> BLOCKED_BY: {}", task.blocked_by.join(", "));
+    }
+    for source in task.sources.iter() {
+        if source.exists() {
+            println!("> SOURCES: {}", source.to_str().unwrap());
+        } else {
+            println!(
+                "> SOURCES: {} {}{}(missing){}",
+                source.to_str().unwrap(),
+                red(),
+                bold(),
+                reset()
+            );
+        }
+    }
+    for span in task.work.iter() {
+        println!("> WORK: {}", span);
+    }
+    for subtask in task.subtasks.iter() {
+        println!("{}", subtask);
+    }
+    if !task.body.is_empty() {
+        println!();
+        println!("{}", task.body);
+    }
+}
+
+/// prints every detail of the selected tasks
+pub fn run(_: &Config, store: &Store, args: &Args) {
+    for (i, task) in super::select(store, &args.selector).iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        task_print(task);
+    }
+}
@@ -1,9 +1,10 @@
 use crate::{
     config::Config,
+    event::Event,
     store::Store,
     task::Task,
     tesc::*,
-    time::{Date, Duration},
+    time::{Date, Duration, Span},
 };
 
 #[derive(Debug, clap::Args)]
@@ -17,6 +18,10 @@ pub struct Args {
     /// only show tasks with the given prefix
     #[clap(long)]
     prefix: Option<String>,
+
+    /// also show tasks which are deferred to a later date
+    #[clap(long)]
+    include_deferred: bool,
 }
 
 fn task_print(task: &Task, prefix_pad: usize) {
@@ -38,6 +43,41 @@ fn task_print(task: &Task, prefix_pad: usize) {
     println!();
 }
 
+fn event_print(event: &Event, span: Span, prefix_pad: usize) {
+    print!(
+        "  {}{}{}{} ",
+        magenta(),
+        event.prefix,
+        reset(),
+        " ".repeat(prefix_pad - event.prefix.len())
+    );
+
+    print!("EVENT ");
+
+    match (span.start, span.end) {
+        (Some(start), Some(end)) => print!("{} - {} ", start, end),
+        (Some(start), None) => print!("{} ", start),
+        _ => (),
+    }
+
+    println!("{}", event.title);
+}
+
+#[derive(Clone, Copy)]
+enum Item<'a> {
+    Task(&'a Task),
+    Event(&'a Event),
+}
+
+impl Item<'_> {
+    fn prefix(&self) -> &str {
+        match self {
+            Item::Task(task) => &task.prefix,
+            Item::Event(event) => &event.prefix,
+        }
+    }
+}
+
 pub fn get_from_and_to(from: Option<&str>, to: Option<&str>) -> (Date, Date) {
     let from_month = |month| {
         let today = Date::today();
@@ -109,6 +149,7 @@ pub fn run(_: &Config, store: &Store, args: &Args) {
     let (mut from, to) = get_from_and_to(args.from.as_deref(), args.to.as_deref());
 
     let prefix = args.prefix.as_deref().unwrap_or("");
+    let today = Date::today();
 
     let get_date = |task: &Task| match (task.scheduled, task.deadline) {
         (Some(date), _) => Some(date),
@@ -116,31 +157,46 @@ pub fn run(_: &Config, store: &Store, args: &Args) {
         _ => None,
     };
 
-    let mut tasks = store
+    let tasks = store
         .tasks
         .iter()
         .filter(|task| task.prefix.starts_with(prefix))
-        .map(|task| (get_date(task), task))
-        .filter(|(a, _)| a.is_some())
-        .map(|(d, t)| (d.unwrap(), t))
+        .filter(|task| args.include_deferred || !task.is_deferred(today))
+        .filter_map(|task| Some((get_date(task)?, Item::Task(task))));
+
+    let events = store
+        .events
+        .iter()
+        .filter(|event| event.prefix.starts_with(prefix))
+        .filter_map(|event| Some((event.at?, Item::Event(event))));
+
+    let mut items = tasks
+        .chain(events)
         .filter(|(d, _)| from <= d.date && d.date <= to)
         .collect::<Vec<_>>();
 
-    tasks.sort_by(|(a, _), (b, _)| a.cmp(b));
+    items.sort_by_key(|(a, _)| *a);
 
-    let prefix_pad = tasks.iter().map(|(_, t)| t.prefix.len()).max().unwrap_or(0);
+    let prefix_pad = items
+        .iter()
+        .map(|(_, i)| i.prefix().len())
+        .max()
+        .unwrap_or(0);
 
     let to = to.add_duration(Duration::Day(1)).unwrap();
 
-    let mut tasks = &tasks[..];
+    let mut items = &items[..];
 
     while from < to {
         println!("{}{}{}", bold(), from.to_pretty_string(), reset());
         from = from.add_duration(Duration::Day(1)).unwrap();
 
-        while !tasks.is_empty() && tasks[0].0.date < from {
-            task_print(tasks[0].1, prefix_pad);
-            tasks = &tasks[1..];
+        while !items.is_empty() && items[0].0.date < from {
+            match items[0].1 {
+                Item::Task(task) => task_print(task, prefix_pad),
+                Item::Event(event) => event_print(event, items[0].0, prefix_pad),
+            }
+            items = &items[1..];
         }
     }
 }
@@ -0,0 +1,41 @@
+use crate::{config::Config, policy, store::Store, time::Date, todo};
+use std::path::PathBuf;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// only check the todos of these files, eg. the staged files in a pre-commit hook. the files
+    /// are read directly and do not have to be inside the base directory
+    files: Vec<PathBuf>,
+}
+
+/// prints a `path:line: rule: message` line for every todo which breaks the policy of the config
+/// and exits with status `1` if there is any
+pub fn run(config: &Config, store: &Store, args: &Args) {
+    let todos = if args.files.is_empty() {
+        store.todos.clone()
+    } else {
+        let options = config.todo_options();
+        args.files
+            .iter()
+            .flat_map(|path| {
+                let body = match std::fs::read_to_string(path) {
+                    Ok(body) => body,
+                    Err(err) => panic!("could not read file: {:?} because: {:?}", path, err),
+                };
+                todo::parse_with(&body, path, &options)
+            })
+            .collect()
+    };
+
+    let violations = policy::check(&todos, &config.todo_policy, Date::today(), |kind| {
+        config.severity(kind)
+    });
+    for violation in violations.iter() {
+        println!("{}", violation);
+    }
+
+    if !violations.is_empty() {
+        eprintln!("found {} violations of the todo policy", violations.len());
+        std::process::exit(1);
+    }
+}
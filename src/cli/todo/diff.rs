@@ -0,0 +1,101 @@
+use super::list;
+use crate::{config::Config, git, tesc::*, todo};
+use std::path::Path;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// the revisions to compare like `main..HEAD`, `main` alone compares `main` with `HEAD` and
+    /// `main...HEAD` compares the common ancestor of both with `HEAD`
+    #[clap(required_unless_present = "staged", conflicts_with = "staged")]
+    range: Option<String>,
+
+    /// compare the staged changes with `HEAD`
+    #[clap(long)]
+    staged: bool,
+}
+
+/// splits a range like `main..HEAD` into its base and head and whether it is written like
+/// `main...HEAD` to compare the merge base of both, an empty revision stands for `HEAD`
+fn split_range(range: &str) -> (&str, &str, bool) {
+    fn or_head(revision: &str) -> &str {
+        if revision.is_empty() {
+            "HEAD"
+        } else {
+            revision
+        }
+    }
+    if let Some((base, head)) = range.split_once("...") {
+        (or_head(base), or_head(head), true)
+    } else if let Some((base, head)) = range.split_once("..") {
+        (or_head(base), or_head(head), false)
+    } else {
+        (range, "HEAD", false)
+    }
+}
+
+/// prints the todos which are added or removed between two revisions of the repository in the
+/// current directory
+pub fn run(config: &Config, args: &Args) {
+    let (base, head, merge_base) = match args.range.as_deref() {
+        Some(range) => split_range(range),
+        // the index is the empty revision
+        None => ("HEAD", "", false),
+    };
+
+    let toplevel = match git::toplevel(Path::new(".")) {
+        Ok(toplevel) => toplevel,
+        Err(err) => panic!("could not find git repository because: {}", err),
+    };
+    let base = if merge_base {
+        match git::merge_base(&toplevel, base, head) {
+            Ok(base) => base,
+            Err(err) => panic!(
+                "could not find the merge base of {:?} and {:?} because: {}",
+                base, head, err
+            ),
+        }
+    } else {
+        base.to_string()
+    };
+    let base = base.as_str();
+    let paths = match git::changed_files(&toplevel, base, head) {
+        Ok(paths) => paths,
+        Err(err) => panic!("could not diff {:?} and {:?} because: {}", base, head, err),
+    };
+
+    let options = config.todo_options();
+    for path in paths {
+        let parse = |revision| {
+            let body = git::show(&toplevel, revision, &path).unwrap_or_default();
+            todo::parse_with(&body, &path, &options)
+        };
+        let old = parse(base);
+        let new = parse(head);
+
+        let (removed, added) = todo::diff(&old, &new);
+        for todo in removed {
+            print!("{}-{} ", red(), reset());
+            list::print(config, todo, true, true);
+        }
+        for todo in added {
+            print!("{}+{} ", green(), reset());
+            list::print(config, todo, true, true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split_range() {
+        assert_eq!(split_range("main"), ("main", "HEAD", false));
+        assert_eq!(split_range("main..dev"), ("main", "dev", false));
+        assert_eq!(split_range("main.."), ("main", "HEAD", false));
+        assert_eq!(split_range("..dev"), ("HEAD", "dev", false));
+        assert_eq!(split_range("main...HEAD"), ("main", "HEAD", true));
+        assert_eq!(split_range("main..."), ("main", "HEAD", true));
+        assert_eq!(split_range("HEAD~2..HEAD"), ("HEAD~2", "HEAD", false));
+    }
+}
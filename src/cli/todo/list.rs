@@ -1,19 +1,191 @@
-use crate::{config::Config, store::Store};
+use crate::{
+    config::Config,
+    report,
+    store::Store,
+    tesc::*,
+    time::{Date, Duration},
+    todo::{Severity, Todo},
+};
+
+/// how the listed todos are grouped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GroupBy {
+    /// group the todos by the file they are in
+    File,
+    /// group the todos by the function, class or impl they are in
+    Symbol,
+}
+
+/// the order of the listed todos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Sort {
+    /// oldest commit first, todos which have not been blamed come last
+    Age,
+}
+
+/// how the todos are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Output {
+    /// a line per todo for the terminal
+    Text,
+    /// a SARIF 2.1.0 log for code scanning tools
+    Sarif,
+    /// a Code Climate report as read by GitLab for code quality
+    Codeclimate,
+}
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
     /// should the paths be listed for the individual todos
     #[clap(long, short)]
     path: bool,
+
+    /// only list todos with the given markers, eg. `--kind FIXME --kind BUG`
+    #[clap(long)]
+    kind: Vec<String>,
+
+    /// print the todos under a heading per file or per enclosing symbol
+    #[clap(long, value_enum)]
+    group_by: Option<GroupBy>,
+
+    /// only list todos whose last commit is older than the given duration, eg. `1y` or `6m`,
+    /// requires `todo_blame = true` in the config
+    #[clap(long)]
+    older_than: Option<String>,
+
+    /// sort the todos instead of listing them in the order they were found
+    #[clap(long, value_enum)]
+    sort: Option<Sort>,
+
+    /// print the todos as a report for static analysis tools, paths inside the current directory
+    /// are written relative to it
+    #[clap(long, value_enum, default_value = "text")]
+    format: Output,
+}
+
+/// returns the heading of the group `todo` belongs to
+fn group(todo: &Todo, group_by: GroupBy) -> String {
+    let path = todo.path.to_string_lossy();
+    match (group_by, todo.symbol.as_deref()) {
+        (GroupBy::Symbol, Some(symbol)) => format!("{} {}", path, symbol),
+        _ => path.into_owned(),
+    }
+}
+
+/// prints a todo on one line followed by its body
+pub(super) fn print(config: &Config, todo: &Todo, path: bool, symbol: bool) {
+    if path {
+        print!("{} ", todo.location());
+    }
+    let color = match config.severity(&todo.kind) {
+        Severity::Info => blue(),
+        Severity::Warning => yellow(),
+        Severity::Error => red(),
+    };
+    print!(
+        "{}{}{}:{} {}",
+        color,
+        bold(),
+        todo.kind,
+        reset(),
+        todo.title
+    );
+    if let Some(owner) = todo.owner.as_deref() {
+        print!(" ({})", owner);
+    }
+    if let Some(issue) = todo.issue.as_deref() {
+        print!(" [{}]", issue);
+    }
+    if let Some(due) = todo.due {
+        print!(" {}due {}{}", red(), due, reset());
+    }
+    if let Some(task) = todo.task.as_deref() {
+        print!(" {}task:{}{}", cyan(), task, reset());
+    }
+    if symbol && let Some(symbol) = todo.symbol.as_deref() {
+        print!(" {}in {}{}", dim(), symbol, reset());
+    }
+    if let Some(committed) = todo.committed {
+        print!(" {}since {}", dim(), committed);
+        if let Some(author) = todo.author.as_deref() {
+            print!(" by {}", author);
+        }
+        print!("{}", reset());
+    }
+    println!();
+    for line in todo.body.lines() {
+        println!("    {}", line);
+    }
 }
 
 /// prints the title of every todo to stdout
-pub fn run(_: &Config, store: &Store, args: &Args) {
-    for todo in store.todos.iter() {
-        if args.path {
-            print!("{}:{} ", todo.path.to_str().unwrap(), todo.line_number);
+pub fn run(config: &Config, store: &Store, args: &Args) {
+    let older_than = args.older_than.as_deref().map(|older_than| {
+        let duration: Duration = older_than
+            .parse()
+            .expect("could not evaluate --older-than flag: expected a duration like '1y'");
+        Date::today()
+            .sub_duration(duration)
+            .expect("could not evaluate --older-than flag")
+    });
+
+    let mut todos = store
+        .todos
+        .iter()
+        .filter(|todo| args.kind.is_empty() || args.kind.contains(&todo.kind))
+        .filter(|todo| match older_than {
+            Some(older_than) => todo
+                .committed
+                .is_some_and(|committed| committed < older_than),
+            None => true,
+        })
+        .collect::<Vec<_>>();
+
+    match args.sort {
+        Some(Sort::Age) => todos.sort_by_key(|todo| (todo.committed.is_none(), todo.committed)),
+        None => (),
+    }
+
+    if args.format != Output::Text {
+        let root = match std::env::current_dir() {
+            Ok(root) => root,
+            Err(err) => panic!("could not read current directory because: {:?}", err),
+        };
+        let severity = |kind: &str| config.severity(kind);
+        let report = match args.format {
+            Output::Sarif => report::sarif(&todos, &root, severity),
+            _ => report::codeclimate(&todos, &root, severity),
+        };
+        // unwrap is ok because a json value always serializes
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+        return;
+    }
+
+    let Some(group_by) = args.group_by else {
+        for todo in todos {
+            print(config, todo, args.path, true);
+        }
+        return;
+    };
+
+    // groups keep the order in which they are first seen
+    let mut groups: Vec<(String, Vec<&Todo>)> = Vec::new();
+    for todo in todos {
+        let heading = group(todo, group_by);
+        match groups.iter_mut().find(|(h, _)| *h == heading) {
+            Some((_, todos)) => todos.push(todo),
+            None => groups.push((heading, vec![todo])),
+        }
+    }
+
+    for (i, (heading, todos)) in groups.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}{}{}", bold(), heading, reset());
+        for todo in todos {
+            print!("  ");
+            print(config, todo, args.path, group_by == GroupBy::File);
         }
-        print!("TODO: {}", todo.title);
-        println!();
     }
 }
@@ -1,6 +1,9 @@
 use crate::{config::Config, store::Store};
 
+mod check;
+mod diff;
 mod list;
+mod promote;
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
@@ -12,10 +15,19 @@ pub struct Args {
 enum Command {
     /// list all todos
     List(list::Args),
+    /// list the todos added and removed between two git revisions
+    Diff(diff::Args),
+    /// check the todos against the rules of `todo_policy` in the config
+    Check(check::Args),
+    /// turn a todo into a task in a markdown file
+    Promote(promote::Args),
 }
 
 pub fn run(config: &Config, store: &Store, args: &Args) {
     match &args.command {
         Command::List(args) => list::run(config, store, args),
+        Command::Diff(args) => diff::run(config, args),
+        Command::Check(args) => check::run(config, store, args),
+        Command::Promote(args) => promote::run(config, store, args),
     }
 }
@@ -0,0 +1,214 @@
+use crate::{
+    config::Config,
+    format::Parser,
+    promote::{self, Draft},
+    schedule::Relative,
+    store::Store,
+    time::Span,
+    todo,
+};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// location of the todo like `src/main.rs:42`
+    location: String,
+
+    /// markdown file the task is added to, it is created if it does not exist
+    #[clap(long)]
+    into: PathBuf,
+
+    /// heading path the task is added under like `Projects/owl`, missing headings are created
+    #[clap(long)]
+    under: String,
+
+    /// deadline of the task, defaults to the due date of the todo
+    #[clap(long)]
+    deadline: Option<String>,
+
+    /// id of the task, defaults to an id made from the title of the todo
+    #[clap(long)]
+    id: Option<String>,
+
+    /// rewrite the todo to `TODO(task:<id>): ...` so that it stays linked to the task
+    #[clap(long)]
+    link: bool,
+}
+
+/// splits a location like `src/main.rs:42` into its path and line number
+fn split_location(location: &str) -> (&str, usize) {
+    let Some((path, line)) = location.rsplit_once(':') else {
+        panic!(
+            "expected a location like 'src/main.rs:42' but got: {:?}",
+            location
+        );
+    };
+    match line.parse() {
+        Ok(line) => (path, line),
+        Err(err) => panic!("could not read line of {:?} because: {:?}", location, err),
+    }
+}
+
+fn read(path: &Path) -> String {
+    match std::fs::read_to_string(path) {
+        Ok(body) => body,
+        Err(err) => panic!("could not read file: {:?} because: {:?}", path, err),
+    }
+}
+
+fn write(path: &Path, body: &str) {
+    if let Err(err) = std::fs::write(path, body) {
+        panic!("could not write file: {:?} because: {:?}", path, err);
+    }
+}
+
+/// returns `body` of the file at `path` with the annotation `task:<id>` added to the marker on
+/// line `line_number`
+///
+/// # Panics
+/// if the file has no such line or the marker is not found on it
+fn link(path: &Path, body: &str, line_number: usize, kind: &str, id: &str) -> String {
+    let mut lines = body
+        .split_inclusive('\n')
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let Some(line) = line_number
+        .checked_sub(1)
+        .and_then(|index| lines.get_mut(index))
+    else {
+        panic!(
+            "could not link the todo at {}:{} to the task because the file has no such line",
+            path.to_string_lossy(),
+            line_number
+        );
+    };
+    let ending = &line[line.trim_end_matches(['\r', '\n']).len()..];
+
+    let Some(linked) = promote::link(&line[..line.len() - ending.len()], kind, id) else {
+        panic!(
+            "could not link the todo at {}:{} to the task because its marker was not found",
+            path.to_string_lossy(),
+            line_number
+        );
+    };
+    *line = format!("{}{}", linked, ending);
+    lines.concat()
+}
+
+/// checks whether `path` is a notebook or a cell of one like `analysis.ipynb#3`
+fn is_notebook(config: &Config, path: &str) -> bool {
+    let notebook = match path.rsplit_once('#') {
+        Some((notebook, cell)) if cell.parse::<usize>().is_ok() => notebook,
+        _ => path,
+    };
+    let format = config.formats.detect(notebook, "");
+    format.map(|format| format.parser) == Some(Parser::Notebook)
+}
+
+/// creates a task from the todo at `args.location` in the markdown file `args.into`
+pub fn run(config: &Config, store: &Store, args: &Args) {
+    let (path, line_number) = split_location(&args.location);
+    if is_notebook(config, path) {
+        panic!("todos in notebooks cannot be promoted: {:?}", args.location);
+    }
+    let path = match std::fs::canonicalize(path) {
+        Ok(path) => path,
+        Err(err) => panic!("could not find file: {:?} because: {:?}", path, err),
+    };
+
+    let source = read(&path);
+    let todos = todo::parse_with(&source, &path, &config.todo_options());
+    let Some(todo) = todos.into_iter().find(|todo| {
+        todo.cell.is_none() && (todo.line_number..=todo.end_line_number).contains(&line_number)
+    }) else {
+        panic!("there is no todo at: {}", args.location);
+    };
+    if let Some(task) = todo.task.as_deref() {
+        panic!("the todo has already been promoted to task:{}", task);
+    }
+
+    if let Some(deadline) = args.deadline.as_deref()
+        && deadline.parse::<Span>().is_err()
+        && deadline.parse::<Relative>().is_err()
+    {
+        panic!("could not parse deadline: {:?}", deadline);
+    }
+
+    let format = config.formats.detect(&args.into, "");
+    if format.map(|format| format.parser) != Some(Parser::Markdown) {
+        panic!("tasks can only be added to markdown files: {:?}", args.into);
+    }
+    let into = match std::env::current_dir() {
+        Ok(directory) => directory.join(&args.into),
+        Err(err) => panic!("could not read current directory because: {:?}", err),
+    };
+    // the parent is canonicalized because `into` may not exist yet
+    let directory = match into.parent().map(std::fs::canonicalize) {
+        Some(Ok(directory)) => directory,
+        _ => panic!("the directory of {:?} does not exist", args.into),
+    };
+    let Some(name) = into.file_name() else {
+        panic!("expected a markdown file but got: {:?}", args.into);
+    };
+    let into = directory.join(name);
+
+    let taken = |id: &str| {
+        store
+            .tasks
+            .iter()
+            .any(|task| task.id.as_deref() == Some(id))
+    };
+    let id = match args.id.as_deref() {
+        Some(id) if taken(id) => panic!("there is already a task with the id: {:?}", id),
+        Some(id) => id.to_string(),
+        None => {
+            let slug = promote::slug(&todo.title);
+            let mut id = slug.clone();
+            let mut n = 1;
+            while taken(&id) {
+                n += 1;
+                id = format!("{}-{}", slug, n);
+            }
+            id
+        }
+    };
+
+    let draft = Draft {
+        title: todo.title.clone(),
+        id: id.clone(),
+        assignee: todo.owner.clone(),
+        deadline: args
+            .deadline
+            .clone()
+            .or(todo.due.map(|due| due.to_string())),
+        source: promote::relative_path(&directory, &path),
+        notes: todo.body.clone(),
+    };
+
+    let body = match std::fs::read_to_string(&into) {
+        Ok(body) => body,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => panic!("could not read file: {:?} because: {:?}", into, err),
+    };
+    let (body, task_line) = match promote::insert(&body, &args.under, &draft) {
+        Ok(inserted) => inserted,
+        Err(err) => panic!("could not add the task to {:?} because: {}", into, err),
+    };
+    // the todo is linked before anything is written, so a failure leaves both files untouched
+    let linked = args
+        .link
+        .then(|| link(&path, &source, todo.line_number, &todo.kind, &id));
+    write(&into, &body);
+
+    if let Some(linked) = linked {
+        write(&path, &linked);
+    }
+
+    println!(
+        "promoted {} to {}:{} as task:{}",
+        todo.location(),
+        into.to_string_lossy(),
+        task_line,
+        id
+    );
+}
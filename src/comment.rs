@@ -0,0 +1,276 @@
+/// the comment syntax of a programming language
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Syntax {
+    /// starts of comments which run until the end of the line, eg. `//`
+    #[serde(default, rename = "line_comments")]
+    pub line: Vec<String>,
+    /// start and end of block comments, eg. `/*` and `*/`
+    #[serde(default, rename = "block_comments")]
+    pub block: Vec<(String, String)>,
+    /// characters which delimit string literals, eg. `"`
+    #[serde(default)]
+    pub quotes: Vec<char>,
+    /// whether `'` starts character literals like `'a'`, other uses of `'` like Rust lifetimes
+    /// are ignored
+    #[serde(default)]
+    pub char_literals: bool,
+    /// whether string literals end at the end of the line, so an unbalanced quote like the
+    /// apostrophe in `echo don't` does not hide the comments of the following lines
+    #[serde(default)]
+    pub line_quotes: bool,
+}
+
+impl Syntax {
+    pub fn new(line: &[&str], block: &[(&str, &str)], quotes: &[char]) -> Self {
+        Self {
+            line: line.iter().map(|l| l.to_string()).collect(),
+            block: block
+                .iter()
+                .map(|(open, close)| (open.to_string(), close.to_string()))
+                .collect(),
+            quotes: quotes.to_vec(),
+            char_literals: false,
+            line_quotes: false,
+        }
+    }
+
+    pub fn with_char_literals(mut self) -> Self {
+        self.char_literals = true;
+        self
+    }
+
+    pub fn with_line_quotes(mut self) -> Self {
+        self.line_quotes = true;
+        self
+    }
+
+    /// the syntax of C and its descendants like Rust, Java or JavaScript
+    pub fn clike() -> Self {
+        Self::new(&["//"], &[("/*", "*/")], &['"', '`']).with_char_literals()
+    }
+}
+
+/// a single line of a comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment<'a> {
+    pub line_number: usize,
+    /// the text of the comment without delimiters, doc comment markers and leading `*` of block
+    /// comments
+    pub text: &'a str,
+    /// the number of whitespace characters between the comment delimiter and the text
+    pub indent: usize,
+}
+
+enum State<'s> {
+    Code,
+    Quote(char),
+    Line,
+    Block(&'s str),
+}
+
+/// checks whether the quote at the start of `rest` opens a character literal like `'a'` or
+/// `'\n'` instead of for example a Rust lifetime
+fn is_char_literal(rest: &str) -> bool {
+    let mut chars = rest.chars().skip(1);
+    match chars.next() {
+        Some('\\') => chars.take(10).any(|c| c == '\''),
+        Some(_) => chars.next() == Some('\''),
+        None => false,
+    }
+}
+
+/// strips delimiters and markers which do not belong to the text of a comment and returns the
+/// text and its indentation
+fn clean(text: &str, block: bool, first: bool) -> (&str, usize) {
+    let text = if !block {
+        text.strip_prefix(['/', '!']).unwrap_or(text)
+    } else if let Some(text) = text.trim_start().strip_prefix('*') {
+        text.trim_start_matches('*')
+    } else {
+        text
+    };
+    let text = if block && first {
+        text.strip_prefix('!').unwrap_or(text)
+    } else {
+        text
+    };
+    (text.trim(), indent(text))
+}
+
+/// the number of whitespace characters at the start of `text`
+pub fn indent(text: &str) -> usize {
+    text.chars().take_while(|c| c.is_whitespace()).count()
+}
+
+/// splits `body` into the lines of its comments, comment delimiters inside string literals are
+/// ignored
+///
+/// # Example
+/// ```
+/// use owl::comment::{comments, Syntax};
+/// let body = "let s = \"// not a comment\"; // a comment\n/**\n * a block\n */";
+///
+/// let got = comments(body, &Syntax::clike())
+///     .into_iter()
+///     .map(|c| (c.line_number, c.text))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(got, vec![(1, "a comment"), (2, ""), (3, "a block"), (4, "")]);
+/// ```
+pub fn comments<'a>(body: &'a str, syntax: &Syntax) -> Vec<Comment<'a>> {
+    let mut comments = Vec::new();
+    let mut state = State::Code;
+    let mut line_number = 1;
+    // start of the current comment line and whether it is the first line of a block comment
+    let mut start = 0;
+    let mut first = true;
+
+    let mut push = |line_number, text: &'a str, block, first| {
+        let (text, indent) = clean(text, block, first);
+        comments.push(Comment {
+            line_number,
+            text,
+            indent,
+        });
+    };
+
+    let mut chars = body.char_indices();
+    while let Some((i, c)) = chars.next() {
+        let rest = &body[i..];
+        match state {
+            State::Code => {
+                // block comments are matched first because they may start like line comments,
+                // eg. `--[[` and `--` in Lua
+                if let Some((open, close)) = syntax
+                    .block
+                    .iter()
+                    .find(|(d, _)| rest.starts_with(d.as_str()))
+                {
+                    state = State::Block(close);
+                    start = i + open.len();
+                    first = true;
+                } else if let Some(delimiter) =
+                    syntax.line.iter().find(|d| rest.starts_with(d.as_str()))
+                {
+                    state = State::Line;
+                    start = i + delimiter.len();
+                } else if syntax.quotes.contains(&c)
+                    || (c == '\'' && syntax.char_literals && is_char_literal(rest))
+                {
+                    state = State::Quote(c);
+                    continue;
+                } else {
+                    if c == '\n' {
+                        line_number += 1;
+                    }
+                    continue;
+                }
+                // skip the remaining characters of the delimiter
+                for _ in 0..body[i..start].chars().count() - 1 {
+                    chars.next();
+                }
+            }
+            State::Quote(quote) => {
+                if c == '\\' {
+                    if let Some((_, '\n')) = chars.next() {
+                        line_number += 1;
+                    }
+                } else if c == quote {
+                    state = State::Code;
+                } else if c == '\n' {
+                    line_number += 1;
+                    if syntax.line_quotes {
+                        state = State::Code;
+                    }
+                }
+            }
+            State::Line => {
+                if c == '\n' {
+                    push(line_number, &body[start..i], false, true);
+                    line_number += 1;
+                    state = State::Code;
+                }
+            }
+            State::Block(close) => {
+                if rest.starts_with(close) {
+                    push(line_number, &body[start..i], true, first);
+                    for _ in 0..close.chars().count() - 1 {
+                        chars.next();
+                    }
+                    state = State::Code;
+                } else if c == '\n' {
+                    push(line_number, &body[start..i], true, first);
+                    line_number += 1;
+                    start = i + 1;
+                    first = false;
+                }
+            }
+        }
+    }
+
+    match state {
+        State::Line => push(line_number, &body[start..], false, true),
+        State::Block(_) => push(line_number, &body[start..], true, first),
+        _ => (),
+    }
+
+    comments
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lines(body: &str) -> Vec<(usize, &str)> {
+        comments(body, &Syntax::clike())
+            .into_iter()
+            .map(|c| (c.line_number, c.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_comments() {
+        let body = r#"/// doc comment
+//! inner doc comment
+fn f<'a>(x: &'a str) -> char { // trailing
+    let s = "/* not a comment \" // still not";
+    let c = '"'; /* block */ let d = '\'';
+    /**
+     * first
+     * second */
+    'x' // end"#;
+
+        assert_eq!(
+            lines(body),
+            vec![
+                (1, "doc comment"),
+                (2, "inner doc comment"),
+                (3, "trailing"),
+                (5, "block"),
+                (6, ""),
+                (7, "first"),
+                (8, "second"),
+                (9, "end"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comments_multiline_string() {
+        let body = "let s = \"first\n// inside\";\n// outside";
+        assert_eq!(lines(body), vec![(3, "outside")]);
+    }
+
+    #[test]
+    fn test_comments_line_quotes() {
+        let body = "echo don't # first\n# second";
+        let syntax = Syntax::new(&["#"], &[], &['"', '\'']);
+        assert_eq!(comments(body, &syntax).len(), 0);
+
+        let got = comments(body, &syntax.with_line_quotes())
+            .into_iter()
+            .map(|c| (c.line_number, c.text))
+            .collect::<Vec<_>>();
+        assert_eq!(got, vec![(2, "second")]);
+    }
+}
@@ -1,10 +1,68 @@
+use crate::format::{Definition, Formats};
+use crate::policy::Policy;
+use crate::todo::{self, Marker};
 use std::path::{Path, PathBuf};
 
+/// which part of the directory of a file, relative to `Config::base_directory`, is prepended to
+/// the prefix of its tasks and events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DirectoryPrefix {
+    #[default]
+    None,
+    /// the whole relative directory
+    Path,
+    /// the first component of the relative directory
+    First,
+    /// the last component of the relative directory
+    Last,
+}
+
+impl DirectoryPrefix {
+    /// returns the prefix for a file at `path` or `None` if nothing should be prepended
+    ///
+    /// # Example
+    /// ```
+    /// use owl::config::DirectoryPrefix;
+    /// let base = "/home/user";
+    /// let path = "/home/user/projects/owl/notes.md";
+    ///
+    /// assert_eq!(DirectoryPrefix::Path.prefix(base, path).as_deref(), Some("projects/owl"));
+    /// assert_eq!(DirectoryPrefix::First.prefix(base, path).as_deref(), Some("projects"));
+    /// assert_eq!(DirectoryPrefix::Last.prefix(base, path).as_deref(), Some("owl"));
+    /// assert_eq!(DirectoryPrefix::None.prefix(base, path), None);
+    /// ```
+    pub fn prefix<B: AsRef<Path>, P: AsRef<Path>>(&self, base: B, path: P) -> Option<String> {
+        let directory = path.as_ref().parent()?.strip_prefix(base.as_ref()).ok()?;
+        let mut components = directory
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned());
+
+        match self {
+            DirectoryPrefix::None => None,
+            DirectoryPrefix::Path => Some(components.collect::<Vec<_>>().join("/")),
+            DirectoryPrefix::First => components.next(),
+            DirectoryPrefix::Last => components.next_back(),
+        }
+        .filter(|prefix| !prefix.is_empty())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub ignore_hidden_files: bool,
     pub ignore: Vec<Vec<u8>>,
     pub base_directory: PathBuf,
+    pub me: Option<String>,
+    pub max_task_body: usize,
+    pub open_command: String,
+    pub directory_prefix: DirectoryPrefix,
+    pub todo_markers: Vec<Marker>,
+    pub todo_inline: bool,
+    pub todo_blame: bool,
+    pub todo_policy: Policy,
+    /// built-in formats and formats of the config
+    pub formats: Formats,
 }
 
 #[derive(Clone, Debug, serde::Deserialize)]
@@ -12,6 +70,32 @@ struct ConfigRaw {
     pub ignore_hidden_files: bool,
     pub ignore: Vec<String>,
     pub base_directory: String,
+    #[serde(default)]
+    pub me: Option<String>,
+    #[serde(default = "default_max_task_body")]
+    pub max_task_body: usize,
+    #[serde(default = "default_open_command")]
+    pub open_command: String,
+    #[serde(default)]
+    pub directory_prefix: DirectoryPrefix,
+    #[serde(default = "todo::default_markers")]
+    pub todo_markers: Vec<Marker>,
+    #[serde(default)]
+    pub todo_inline: bool,
+    #[serde(default)]
+    pub todo_blame: bool,
+    #[serde(default)]
+    pub todo_policy: Policy,
+    #[serde(default)]
+    pub formats: Vec<Definition>,
+}
+
+fn default_max_task_body() -> usize {
+    4096
+}
+
+fn default_open_command() -> String {
+    "xdg-open".into()
 }
 
 fn unenvar_path(path: &str) -> String {
@@ -38,12 +122,40 @@ fn unenvar_path(path: &str) -> String {
 }
 
 impl Config {
+    /// returns the severity of todos with the marker `kind`
+    pub fn severity(&self, kind: &str) -> todo::Severity {
+        self.todo_markers
+            .iter()
+            .find(|marker| marker.name == kind)
+            .map(|marker| marker.severity)
+            .unwrap_or_default()
+    }
+
+    /// returns the options of the todo parser as configured
+    pub fn todo_options(&self) -> todo::Options {
+        todo::Options {
+            markers: self.todo_markers.clone(),
+            formats: self.formats.clone(),
+            inline: self.todo_inline,
+        }
+    }
+
+    /// returns a fingerprint of the settings which change what is parsed from files, the store
+    /// remembers it to reparse every file when one of them changes
+    pub fn fingerprint(&self) -> String {
+        let settings = format!(
+            "{:?}\0{:?}\0{:?}\0{}\0{:?}",
+            self.todo_markers, self.formats, self.todo_inline, self.max_task_body, self.todo_blame
+        );
+        format!("{:016x}", crate::report::fnv1a(settings.as_bytes()))
+    }
+
     /// creates a default config at path and returns it
     ///
     /// # Panics
     /// if an error occures this function panics with a message
     pub fn create_default(path: &Path) -> Self {
-        let default_body = r#"# Default Owl Config
+        let default_body = r##"# Default Owl Config
 
 # base directory from which the scan for todos and tasks starts
 base_directory = "$HOME"
@@ -62,7 +174,80 @@ ignore = [
 
 # whether or not hidden files and directories should be ignored
 ignore_hidden_files = true
-"#;
+
+# your name as used in `> ASSIGNEE:` lines and `@name` mentions of tasks, used by
+# `owl agenda --mine`
+# me = "alice"
+
+# maximum number of bytes of the notes below a task heading which are kept in the store
+max_task_body = 4096
+
+# command used by `owl task open-sources` to open the sources of a task, the path of the source is
+# appended as last argument
+open_command = "xdg-open"
+
+# which part of the directory of a file relative to the base directory is prepended to the prefix
+# of its tasks, one of:
+# - "none"  nothing is prepended
+# - "path"  the whole directory, eg. "projects/owl"
+# - "first" the first directory, eg. "projects"
+# - "last"  the last directory, eg. "owl"
+# references in `BLOCKED_BY` and `after(...)` may leave the directory out
+directory_prefix = "none"
+
+# markers which start a todo when they are followed by a `:`, eg. `// FIXME: ...`. the severity
+# is one of "info", "warning" or "error" and is used to colour and filter todos. add eg.
+# `{ name = "NOTE", severity = "info" }` to collect notes as well
+todo_markers = [
+    { name = "TODO", severity = "info" },
+    { name = "HACK", severity = "warning" },
+    { name = "XXX", severity = "warning" },
+    { name = "FIXME", severity = "error" },
+    { name = "BUG", severity = "error" },
+]
+
+# whether markers in the middle of paragraphs of markdown and typst files start todos, eg.
+# `This section TODO: needs an example`. headings, list items and comments are always scanned
+todo_inline = false
+
+# whether todos in git repositories record the author and date of the commit which last changed
+# them, which runs `git blame` on every file with todos when it changes
+todo_blame = false
+
+# additional file formats, a format with the name of a built-in format (eg. "rust", "python" or
+# "markdown") replaces it. files are matched by `extensions`, `filenames`, `globs` or the
+# `interpreters` of their shebang line. `parser` is one of "code", "markdown", "typst" or "latex",
+# tasks and events are only read from files with the "markdown" parser. todos in code are found in the comments described by `line_comments` and `block_comments`
+# while comment delimiters inside `quotes` are ignored, `line_quotes = true` ends quotes at the end
+# of the line. `scopes` is "braces" or "indentation" if the function or class enclosing a todo
+# should be recorded
+#
+# [[formats]]
+# name = "terraform"
+# extensions = ["tf", "tfvars"]
+# line_comments = ["#", "//"]
+# block_comments = [["/*", "*/"]]
+# quotes = ['"']
+# scopes = "braces"
+
+# rules checked by `owl todo check`, which exits with an error if a todo breaks one of them.
+# `require_owner` and `require_issue` need annotations like `FIXME(alice, #123): ...` for every
+# marker, or for markers with at least `min_severity` if it is set to "info", "warning" or "error".
+# `forbid_overdue` reports todos past their due date and `max_per_file` limits the todos in a file.
+# `forbid` lists markers which are not allowed in the files matching `globs`, or anywhere if there
+# are no globs
+#
+# [todo_policy]
+# require_owner = false
+# require_issue = true
+# min_severity = "warning"
+# forbid_overdue = true
+# max_per_file = 10
+#
+# [[todo_policy.forbid]]
+# markers = ["XXX"]
+# globs = ["**/src/**"]
+"##;
         assert!(path.is_absolute());
 
         // this unwrap should be safe because no relative path should every be fed here
@@ -133,6 +318,15 @@ ignore_hidden_files = true
             ignore_hidden_files,
             ignore,
             base_directory,
+            me: config.me,
+            max_task_body: config.max_task_body,
+            open_command: config.open_command,
+            directory_prefix: config.directory_prefix,
+            todo_markers: config.todo_markers,
+            todo_inline: config.todo_inline,
+            todo_blame: config.todo_blame,
+            todo_policy: config.todo_policy,
+            formats: Formats::new(config.formats),
         }
     }
 }
@@ -0,0 +1,222 @@
+use crate::task::Task;
+
+/// dependency graph between tasks built from their `BLOCKED_BY` references
+#[derive(Debug, Clone)]
+pub struct Graph<'a> {
+    pub tasks: &'a [Task],
+    /// `blockers[i]` holds the indices of every task which blocks `tasks[i]`
+    pub blockers: Vec<Vec<usize>>,
+    /// references which could not be resolved as `(index of task, reference)`
+    pub unresolved: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Graph<'a> {
+    /// resolves the `blocked_by` references of every task in `tasks`
+    ///
+    /// a reference resolves to every task it matches, see `Task::matches`
+    pub fn new(tasks: &'a [Task]) -> Self {
+        let mut blockers = vec![Vec::new(); tasks.len()];
+        let mut unresolved = Vec::new();
+
+        for (i, task) in tasks.iter().enumerate() {
+            for reference in task.blocked_by.iter() {
+                let len = blockers[i].len();
+                blockers[i].extend(
+                    tasks
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, other)| other.matches(reference))
+                        .map(|(j, _)| j),
+                );
+                if blockers[i].len() == len {
+                    log::warn!(
+                        "could not resolve dependency {:?} of task {:?}",
+                        reference,
+                        task.heading_path()
+                    );
+                    unresolved.push((i, reference.as_str()));
+                }
+            }
+            blockers[i].sort();
+            blockers[i].dedup();
+        }
+
+        Self {
+            tasks,
+            blockers,
+            unresolved,
+        }
+    }
+
+    /// a task is blocked if at least one of the tasks it depends on is still open
+    pub fn is_blocked(&self, index: usize) -> bool {
+        self.blockers[index]
+            .iter()
+            .any(|&i| self.tasks[i].state.is_open())
+    }
+
+    /// returns every dependency cycle as a list of task indices
+    ///
+    /// # Example
+    /// ```
+    /// use owl::{deps::Graph, task::Task};
+    /// let body = "# TASK: A\n> BLOCKED_BY: B\n# TASK: B\n> BLOCKED_BY: A";
+    /// let tasks = Task::parse(body, "/home/user/cycle.md");
+    /// let graph = Graph::new(&tasks);
+    /// assert_eq!(graph.cycles(), vec![vec![0, 1]]);
+    /// ```
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: 0,
+            indices: vec![None; self.tasks.len()],
+            lowlinks: vec![0; self.tasks.len()],
+            stack: Vec::new(),
+            on_stack: vec![false; self.tasks.len()],
+            components: Vec::new(),
+        };
+
+        for node in 0..self.tasks.len() {
+            if tarjan.indices[node].is_none() {
+                tarjan.connect(node);
+            }
+        }
+
+        let mut cycles = tarjan
+            .components
+            .into_iter()
+            .filter(|c| c.len() > 1 || self.blockers[c[0]].contains(&c[0]))
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .collect::<Vec<_>>();
+        cycles.sort();
+        cycles
+    }
+
+    /// exports the graph in the Graphviz DOT format, edges point from the blocking task to the
+    /// blocked task
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph tasks {\n");
+        for (i, task) in self.tasks.iter().enumerate() {
+            let involved = !self.blockers[i].is_empty()
+                || self.blockers.iter().any(|blockers| blockers.contains(&i));
+            if !involved {
+                continue;
+            }
+            let style = if task.state.is_closed() {
+                ", style=dashed"
+            } else {
+                ""
+            };
+            dot.push_str(&format!(
+                "    t{} [label={:?}{}];\n",
+                i,
+                task.heading_path(),
+                style
+            ));
+        }
+        for (i, blockers) in self.blockers.iter().enumerate() {
+            for j in blockers.iter() {
+                dot.push_str(&format!("    t{} -> t{};\n", j, i));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// state of Tarjan's strongly connected components algorithm
+struct Tarjan<'g, 'a> {
+    graph: &'g Graph<'a>,
+    index: usize,
+    indices: Vec<Option<usize>>,
+    lowlinks: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_, '_> {
+    fn connect(&mut self, node: usize) {
+        self.indices[node] = Some(self.index);
+        self.lowlinks[node] = self.index;
+        self.index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for &next in self.graph.blockers[node].iter() {
+            match self.indices[next] {
+                None => {
+                    self.connect(next);
+                    self.lowlinks[node] = self.lowlinks[node].min(self.lowlinks[next]);
+                }
+                Some(index) if self.on_stack[next] => {
+                    self.lowlinks[node] = self.lowlinks[node].min(index);
+                }
+                Some(_) => (),
+            }
+        }
+
+        if Some(self.lowlinks[node]) == self.indices[node] {
+            let mut component = Vec::new();
+            while let Some(top) = self.stack.pop() {
+                self.on_stack[top] = false;
+                component.push(top);
+                if top == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_graph_blocked() {
+        let body = "
+# Uni
+## DONE: Book room
+> ID: room
+## TASK: Write exam
+> BLOCKED_BY: room
+## TASK: Grade exam
+> BLOCKED_BY: Uni/Write exam, unknown
+";
+        let tasks = Task::parse(body, "/home/user/uni.md");
+        let graph = Graph::new(&tasks);
+
+        assert_eq!(graph.blockers, vec![vec![], vec![0], vec![1]]);
+        assert_eq!(graph.unresolved, vec![(2, "unknown")]);
+        assert!(!graph.is_blocked(0));
+        assert!(!graph.is_blocked(1));
+        assert!(graph.is_blocked(2));
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn test_graph_cycles() {
+        let body = "
+# TASK: A
+> BLOCKED_BY: C
+# TASK: B
+> BLOCKED_BY: A
+# TASK: C
+> BLOCKED_BY: B
+# TASK: D
+> BLOCKED_BY: D
+# TASK: E
+> BLOCKED_BY: A
+";
+        let tasks = Task::parse(body, "/home/user/cycle.md");
+        let graph = Graph::new(&tasks);
+
+        assert_eq!(graph.cycles(), vec![vec![0, 1, 2], vec![3]]);
+        assert!(graph.is_blocked(4));
+    }
+}
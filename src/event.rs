@@ -0,0 +1,158 @@
+use crate::{
+    format::{Formats, Parser},
+    frontmatter::FrontMatter,
+    task::{PrefixBuffer, heading},
+    time::Span,
+};
+use std::path::{Path, PathBuf};
+
+/// an appointment like a meeting or a lecture, unlike a task it cannot be done and therefore
+/// never becomes overdue
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Event {
+    pub path: PathBuf,
+    pub prefix: String,
+    pub title: String,
+    pub at: Option<Span>,
+    pub line_number: usize,
+}
+
+impl Event {
+    /// Parses a markdown file into a list of events with associated prefixes
+    ///
+    /// # Example
+    /// ```
+    /// use owl::event::Event;
+    /// let body = "
+    /// ## Uni
+    /// ### Course 1
+    /// #### EVENT: Lecture
+    /// > AT: 2025-07-01 10:00 - 12:00
+    /// ";
+    ///
+    /// let expected = Event {
+    ///     prefix: "Uni/Course 1".into(),
+    ///     title: "Lecture".into(),
+    ///     path: "/home/user/journal/uni.md".into(),
+    ///     at: "2025-07-01 10:00 - 12:00".parse().ok(),
+    ///     line_number: 4,
+    /// };
+    ///
+    /// let got = Event::parse(body, "/home/user/journal/uni.md");
+    /// assert_eq!(vec![expected], got);
+    /// ```
+    ///
+    /// # Errors
+    /// erroniously formatted events will be ignored while parsing
+    pub fn parse<P: AsRef<Path>>(body: &str, path: P) -> Vec<Event> {
+        Self::parse_with(body, path, &Formats::default())
+    }
+
+    /// like `parse` but detects the format of the file with `formats`, eg. the formats of the
+    /// config
+    pub fn parse_with<P: AsRef<Path>>(body: &str, path: P, formats: &Formats) -> Vec<Event> {
+        let path: &Path = path.as_ref();
+        if formats.detect(path, body).map(|format| format.parser) != Some(Parser::Markdown) {
+            return Vec::new();
+        }
+
+        let (front_matter, front_matter_len) = FrontMatter::parse(body);
+        if front_matter.skip_tasks {
+            return Vec::new();
+        }
+
+        let mut prefix = PrefixBuffer::new();
+        if let Some(default_prefix) = front_matter.prefix.as_deref() {
+            prefix.push(0, default_prefix);
+        }
+        let mut events = Vec::new();
+        let mut in_event = false;
+        let body = body
+            .lines()
+            .enumerate()
+            .map(|(n, l)| (n + 1, l))
+            .skip(front_matter_len);
+
+        for (line_number, line) in body {
+            if let Some((level, line)) = heading(line) {
+                let line = line.trim();
+                prefix.pop_to(level);
+                in_event = false;
+
+                if let Some(title) = line.strip_prefix("EVENT:") {
+                    in_event = true;
+                    events.push(Event {
+                        path: path.into(),
+                        prefix: prefix.read(),
+                        title: title.trim().into(),
+                        at: None,
+                        line_number,
+                    });
+                } else if !line.starts_with("TASK:") && !line.starts_with("DONE:") {
+                    prefix.push(level, line);
+                }
+                continue;
+            }
+
+            if !in_event {
+                continue;
+            }
+
+            if let Some(at) = line
+                .strip_prefix(">")
+                .and_then(|line| line.trim().strip_prefix("AT:"))
+            {
+                let at = match at.trim().parse::<Span>() {
+                    Ok(at) => at,
+                    Err(err) => {
+                        log::warn!("ignoring parsing error in at: {:?}", err);
+                        continue;
+                    }
+                };
+                if let Some(event) = events.last_mut() {
+                    event.at = Some(at);
+                }
+            }
+        }
+        log::info!("parsed events from file: {:?}", path);
+        events
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_event_parse() {
+        let path = "/home/to/some/folder.md";
+        let body = "
+# Uni
+## TASK: Exercise 1
+> AT: 2025-07-01
+## EVENT: Lecture
+> AT: 2025-07-02 10:00 - 12:00
+## Course 2
+### EVENT: Exam
+> AT: 2025-07-03 08:00
+";
+        let expected = vec![
+            Event {
+                path: path.into(),
+                prefix: "Uni".into(),
+                title: "Lecture".into(),
+                at: "2025-07-02 10:00 - 12:00".parse().ok(),
+                line_number: 5,
+            },
+            Event {
+                path: path.into(),
+                prefix: "Uni/Course 2".into(),
+                title: "Exam".into(),
+                at: "2025-07-03 08:00".parse().ok(),
+                line_number: 8,
+            },
+        ];
+
+        assert_eq!(expected, Event::parse(body, path));
+    }
+}
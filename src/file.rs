@@ -5,4 +5,12 @@ use std::time::SystemTime;
 pub struct File {
     pub path: PathBuf,
     pub mtime: SystemTime,
+    /// whether the todos of the file have been blamed, files with todos which have not been
+    /// committed yet are blamed again by the next scan
+    #[serde(default)]
+    pub blamed: bool,
+    /// whether the file was read so shortly after it changed that a later change may keep the
+    /// same mtime, such files are parsed again by the next scan
+    #[serde(default)]
+    pub reparse: bool,
 }
@@ -1,4 +1,8 @@
+use crate::comment::Syntax;
+use crate::symbol::Scopes;
+use std::io::BufRead;
 use std::path::Path;
+use std::sync::LazyLock;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Format {
@@ -20,8 +24,183 @@ pub enum Format {
     Python,
 
     Nix,
+
+    Lua,
+    Sql,
+    Haskell,
+    Html,
+    Xml,
+    Vue,
+    Yaml,
+    Toml,
+    Ini,
+    Css,
+    Scss,
+    Kotlin,
+    Swift,
+    Zig,
+    Ruby,
+    Elixir,
+
+    Makefile,
+    Dockerfile,
+    CMake,
+
+    Notebook,
+}
+
+/// how todos are found in files of a format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Parser {
+    /// todos are written in comments as described by the comment syntax of the format
+    #[default]
+    Code,
+    Markdown,
+    Typst,
+    Latex,
+    /// Jupyter notebooks, markdown cells are parsed as markdown and code cells with the format
+    /// named like the language of the kernel
+    Notebook,
+}
+
+/// describes which files belong to a format and how todos are written in them, the built-in
+/// formats and the formats of the config are both definitions
+///
+/// ```toml
+/// [[formats]]
+/// name = "terraform"
+/// extensions = ["tf", "tfvars"]
+/// line_comments = ["#", "//"]
+/// block_comments = [["/*", "*/"]]
+/// quotes = ['"']
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Definition {
+    pub name: String,
+    /// file extensions without the leading `.`
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// complete file names like `Makefile`
+    #[serde(default)]
+    pub filenames: Vec<String>,
+    /// glob patterns matched against the whole path
+    #[serde(default)]
+    pub globs: Vec<String>,
+    /// interpreters in the shebang line of scripts without an extension
+    #[serde(default)]
+    pub interpreters: Vec<String>,
+    #[serde(default)]
+    pub parser: Parser,
+    #[serde(flatten)]
+    pub syntax: Syntax,
+    /// how the symbol enclosing a todo is found, one of `none`, `braces` or `indentation`
+    #[serde(default)]
+    pub scopes: Scopes,
 }
 
+impl Definition {
+    fn new(name: &str, extensions: &[&str], parser: Parser, syntax: Syntax) -> Self {
+        let strings = |s: &[&str]| s.iter().map(|s| s.to_string()).collect();
+        Self {
+            name: name.into(),
+            extensions: strings(extensions),
+            filenames: Vec::new(),
+            globs: Vec::new(),
+            interpreters: Vec::new(),
+            parser,
+            syntax,
+            scopes: Scopes::None,
+        }
+    }
+
+    fn with_filenames(mut self, filenames: &[&str]) -> Self {
+        self.filenames = filenames.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    fn with_scopes(mut self, scopes: Scopes) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    fn with_interpreters(mut self, interpreters: &[&str]) -> Self {
+        self.interpreters = interpreters.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// checks whether the file at `path` belongs to this format by its name, extension or globs
+    pub fn matches<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path: &Path = path.as_ref();
+        let name = path.file_name().and_then(|name| name.to_str());
+        let extension = path.extension().and_then(|ext| ext.to_str());
+
+        name.is_some_and(|name| self.filenames.iter().any(|n| n == name))
+            || extension.is_some_and(|ext| self.extensions.iter().any(|e| e == ext))
+            || self.globs.iter().any(|glob| {
+                fast_glob::glob_match(glob.as_bytes(), path.as_os_str().as_encoded_bytes())
+            })
+    }
+}
+
+/// returns the name of the interpreter of a shebang line, eg. `python` for
+/// `#!/usr/bin/env python3`
+fn interpreter(line: &str) -> Option<&str> {
+    let line = line.strip_prefix("#!")?;
+
+    let mut words = line.split_whitespace();
+    let mut interpreter = words.next()?;
+    if interpreter.ends_with("/env") {
+        interpreter = words.find(|w| !w.starts_with('-'))?;
+    }
+    let interpreter = interpreter.rsplit('/').next()?;
+    // ignore versions like in `python3.12`
+    Some(interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.'))
+}
+
+const ALL: [Format; 34] = [
+    Format::Markdown,
+    Format::Typst,
+    Format::Latex,
+    Format::Java,
+    Format::JavaScript,
+    Format::Go,
+    Format::C,
+    Format::CPP,
+    Format::Rust,
+    Format::Typescript,
+    Format::Shell,
+    Format::CSharp,
+    Format::Python,
+    Format::Nix,
+    Format::Lua,
+    Format::Sql,
+    Format::Haskell,
+    Format::Html,
+    Format::Xml,
+    Format::Vue,
+    Format::Yaml,
+    Format::Toml,
+    Format::Ini,
+    Format::Css,
+    Format::Scss,
+    Format::Kotlin,
+    Format::Swift,
+    Format::Zig,
+    Format::Ruby,
+    Format::Elixir,
+    Format::Makefile,
+    Format::Dockerfile,
+    Format::CMake,
+    Format::Notebook,
+];
+
+static BUILTIN: LazyLock<Vec<(Format, Definition)>> = LazyLock::new(|| {
+    ALL.iter()
+        .filter_map(|format| Some((*format, format.definition()?)))
+        .collect()
+});
+
 impl Format {
     pub fn is_unknown(&self) -> bool {
         *self == Format::Unknown
@@ -31,29 +210,347 @@ impl Format {
         !self.is_unknown()
     }
 
+    /// detects the built-in format of `path` by its file name or extension
     pub fn new<P: AsRef<Path>>(path: P) -> Self {
         let path: &Path = path.as_ref();
-        match path.extension().and_then(|ext| ext.to_str()) {
-            Some("md") => Self::Markdown,
-            Some("typ") => Self::Typst,
-            Some("java") => Self::Java,
-            Some("js") => Self::JavaScript,
-            Some("go") => Self::Go,
-            Some("cpp") => Self::CPP,
-            Some("hpp") => Self::CPP,
-            Some("cc") => Self::CPP,
-            Some("cxx") => Self::CPP,
-            Some("c") => Self::C,
-            Some("h") => Self::C,
-            Some("rs") => Self::Rust,
-            Some("ts") => Self::Typescript,
-            Some("sh") => Self::Shell,
-            Some("cs") => Self::CSharp,
-            Some("py") => Self::Python,
-            Some("nix") => Self::Nix,
-            Some("latex") => Self::Latex,
-            Some("tex") => Self::Latex,
-            _ => Self::Unknown,
+        BUILTIN
+            .iter()
+            .find(|(_, definition)| definition.matches(path))
+            .map(|(format, _)| *format)
+            .unwrap_or(Self::Unknown)
+    }
+
+    /// detects the built-in format of a script by the interpreter in its shebang line
+    ///
+    /// # Example
+    /// ```
+    /// use owl::format::Format;
+    /// assert_eq!(Format::from_shebang("#!/usr/bin/env python3"), Format::Python);
+    /// assert_eq!(Format::from_shebang("#!/bin/bash -e"), Format::Shell);
+    /// assert_eq!(Format::from_shebang("# not a shebang"), Format::Unknown);
+    /// ```
+    pub fn from_shebang(line: &str) -> Self {
+        let Some(interpreter) = interpreter(line) else {
+            return Self::Unknown;
+        };
+        BUILTIN
+            .iter()
+            .find(|(_, definition)| definition.interpreters.iter().any(|i| i == interpreter))
+            .map(|(format, _)| *format)
+            .unwrap_or(Self::Unknown)
+    }
+
+    /// the definition of a built-in format, `Format::Unknown` has none
+    pub fn definition(&self) -> Option<Definition> {
+        use Parser::*;
+        let hash = || Syntax::new(&["#"], &[], &['"', '\'']);
+        // unquoted words like `don't` are common in shell-like formats, so their quotes end at
+        // the end of the line
+        let shell = || hash().with_line_quotes();
+
+        let definition =
+            match self {
+                Self::Unknown => return None,
+                Self::Markdown => Definition::new(
+                    "markdown",
+                    &["md"],
+                    Markdown,
+                    Syntax::new(&[], &[("<!--", "-->")], &[]),
+                ),
+                Self::Typst => Definition::new(
+                    "typst",
+                    &["typ"],
+                    Typst,
+                    Syntax::new(&["//"], &[("/*", "*/")], &['"']),
+                ),
+                Self::Latex => {
+                    Definition::new("latex", &["tex", "latex"], Latex, Syntax::default())
+                }
+                Self::Notebook => {
+                    Definition::new("notebook", &["ipynb"], Notebook, Syntax::default())
+                }
+
+                Self::Java => Definition::new("java", &["java"], Code, Syntax::clike())
+                    .with_scopes(Scopes::Braces),
+                Self::JavaScript => {
+                    Definition::new("javascript", &["js", "mjs", "cjs"], Code, Syntax::clike())
+                        .with_scopes(Scopes::Braces)
+                        .with_interpreters(&["node", "deno", "bun"])
+                }
+                Self::Go => Definition::new("go", &["go"], Code, Syntax::clike())
+                    .with_scopes(Scopes::Braces),
+                Self::C => Definition::new("c", &["c", "h"], Code, Syntax::clike())
+                    .with_scopes(Scopes::Braces),
+                Self::CPP => {
+                    Definition::new("cpp", &["cpp", "hpp", "cc", "cxx"], Code, Syntax::clike())
+                        .with_scopes(Scopes::Braces)
+                }
+                Self::Rust => Definition::new("rust", &["rs"], Code, Syntax::clike())
+                    .with_scopes(Scopes::Braces),
+                Self::Typescript => Definition::new("typescript", &["ts"], Code, Syntax::clike())
+                    .with_scopes(Scopes::Braces),
+                Self::CSharp => Definition::new("csharp", &["cs"], Code, Syntax::clike())
+                    .with_scopes(Scopes::Braces),
+                Self::Kotlin => Definition::new("kotlin", &["kt", "kts"], Code, Syntax::clike())
+                    .with_scopes(Scopes::Braces),
+                Self::Swift => Definition::new("swift", &["swift"], Code, Syntax::clike())
+                    .with_scopes(Scopes::Braces),
+                Self::Zig => Definition::new("zig", &["zig"], Code, Syntax::clike())
+                    .with_scopes(Scopes::Braces),
+
+                Self::Css => Definition::new(
+                    "css",
+                    &["css"],
+                    Code,
+                    Syntax::new(&[], &[("/*", "*/")], &['"', '\'']),
+                ),
+                Self::Scss => Definition::new(
+                    "scss",
+                    &["scss"],
+                    Code,
+                    Syntax::new(&["//"], &[("/*", "*/")], &['"', '\'']),
+                ),
+
+                Self::Shell => Definition::new("shell", &["sh", "bash", "zsh"], Code, shell())
+                    .with_interpreters(&["sh", "bash", "zsh", "dash", "ksh", "fish"]),
+                Self::Python => Definition::new("python", &["py"], Code, hash())
+                    .with_interpreters(&["python"])
+                    .with_scopes(Scopes::Indentation),
+                Self::Ruby => {
+                    Definition::new("ruby", &["rb"], Code, hash()).with_interpreters(&["ruby"])
+                }
+                Self::Elixir => Definition::new("elixir", &["ex", "exs"], Code, hash())
+                    .with_interpreters(&["elixir"]),
+                Self::Makefile => Definition::new("makefile", &["mk"], Code, shell())
+                    .with_filenames(&["Makefile", "makefile", "GNUmakefile"]),
+                Self::Dockerfile => Definition::new("dockerfile", &[], Code, shell())
+                    .with_filenames(&["Dockerfile", "Containerfile"]),
+                Self::CMake => Definition::new("cmake", &["cmake"], Code, shell())
+                    .with_filenames(&["CMakeLists.txt"]),
+
+                Self::Yaml => Definition::new(
+                    "yaml",
+                    &["yaml", "yml"],
+                    Code,
+                    Syntax::new(&["#"], &[], &['"']),
+                ),
+                Self::Toml => {
+                    Definition::new("toml", &["toml"], Code, Syntax::new(&["#"], &[], &['"']))
+                }
+                Self::Ini => Definition::new(
+                    "ini",
+                    &["ini", "cfg"],
+                    Code,
+                    Syntax::new(&["#", ";"], &[], &['"']),
+                ),
+                Self::Nix => Definition::new(
+                    "nix",
+                    &["nix"],
+                    Code,
+                    Syntax::new(&["#"], &[("/*", "*/")], &['"']),
+                )
+                .with_interpreters(&["nix-shell"]),
+
+                Self::Lua => Definition::new(
+                    "lua",
+                    &["lua"],
+                    Code,
+                    Syntax::new(&["--"], &[("--[[", "]]")], &['"', '\'']),
+                )
+                .with_interpreters(&["lua", "luajit"]),
+                Self::Sql => Definition::new(
+                    "sql",
+                    &["sql"],
+                    Code,
+                    Syntax::new(&["--"], &[("/*", "*/")], &['\'']),
+                ),
+                Self::Haskell => Definition::new(
+                    "haskell",
+                    &["hs"],
+                    Code,
+                    Syntax::new(&["--"], &[("{-", "-}")], &['"']).with_char_literals(),
+                )
+                .with_interpreters(&["runhaskell", "runghc"]),
+
+                Self::Html => Definition::new(
+                    "html",
+                    &["html", "htm"],
+                    Code,
+                    Syntax::new(&[], &[("<!--", "-->")], &[]),
+                ),
+                Self::Xml => Definition::new(
+                    "xml",
+                    &["xml", "svg"],
+                    Code,
+                    Syntax::new(&[], &[("<!--", "-->")], &[]),
+                ),
+                Self::Vue => Definition::new(
+                    "vue",
+                    &["vue"],
+                    Code,
+                    // apostrophes in the text of templates must not hide the following lines
+                    Syntax::new(&["//"], &[("<!--", "-->"), ("/*", "*/")], &['"', '\''])
+                        .with_line_quotes(),
+                ),
+            };
+        Some(definition)
+    }
+}
+
+/// the formats known to owl, formats of the config come before the built-in formats and replace
+/// built-in formats with the same name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Formats {
+    definitions: Vec<Definition>,
+}
+
+impl Default for Formats {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl Formats {
+    pub fn new(definitions: Vec<Definition>) -> Self {
+        let builtin = BUILTIN
+            .iter()
+            .map(|(_, definition)| definition)
+            .filter(|builtin| !definitions.iter().any(|d| d.name == builtin.name))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut definitions = definitions;
+        definitions.extend(builtin);
+        Self { definitions }
+    }
+
+    /// returns the format of `path` whose content is `body`, files without an extension are
+    /// detected by their shebang line
+    ///
+    /// # Example
+    /// ```
+    /// use owl::format::Formats;
+    /// let formats = Formats::default();
+    ///
+    /// let format = formats.detect("/bin/deploy", "#!/usr/bin/env bash\n").unwrap();
+    /// assert_eq!(format.name, "shell");
+    /// assert!(formats.detect("/notes.txt", "").is_none());
+    /// ```
+    pub fn detect<P: AsRef<Path>>(&self, path: P, body: &str) -> Option<&Definition> {
+        let path: &Path = path.as_ref();
+        self.by_path(path).or_else(|| {
+            path.extension()
+                .is_none()
+                .then(|| self.by_shebang(body.lines().next().unwrap_or("")))
+                .flatten()
+        })
+    }
+
+    /// like `detect` but only reads the first line of the file at `path` if necessary
+    ///
+    /// # Errors
+    /// files which cannot be read have no format
+    pub fn detect_file<P: AsRef<Path>>(&self, path: P) -> Option<&Definition> {
+        let path: &Path = path.as_ref();
+        if let Some(definition) = self.by_path(path) {
+            return Some(definition);
+        }
+        if path.extension().is_some() {
+            return None;
         }
+
+        let mut line = String::new();
+        std::fs::File::open(path)
+            .map(std::io::BufReader::new)
+            .and_then(|mut reader| reader.read_line(&mut line))
+            .ok()?;
+        self.by_shebang(&line)
+    }
+
+    /// returns the format called `name`, eg. the language of a notebook kernel
+    pub fn by_name(&self, name: &str) -> Option<&Definition> {
+        self.definitions
+            .iter()
+            .find(|d| d.name.eq_ignore_ascii_case(name))
+    }
+
+    fn by_path(&self, path: &Path) -> Option<&Definition> {
+        self.definitions.iter().find(|d| d.matches(path))
+    }
+
+    fn by_shebang(&self, line: &str) -> Option<&Definition> {
+        let interpreter = interpreter(line)?;
+        self.definitions
+            .iter()
+            .find(|d| d.interpreters.iter().any(|i| i == interpreter))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(Format::new("/src/main.rs"), Format::Rust);
+        assert_eq!(Format::new("/src/init.lua"), Format::Lua);
+        assert_eq!(Format::new("/src/App.vue"), Format::Vue);
+        assert_eq!(Format::new("/src/Makefile"), Format::Makefile);
+        assert_eq!(Format::new("/src/Dockerfile"), Format::Dockerfile);
+        assert_eq!(Format::new("/src/CMakeLists.txt"), Format::CMake);
+        assert_eq!(Format::new("/src/notes.txt"), Format::Unknown);
+        assert_eq!(Format::new("/src/run"), Format::Unknown);
+    }
+
+    #[test]
+    fn test_detect() {
+        let formats = Formats::default();
+        let name = |path: &str, body: &str| formats.detect(path, body).map(|d| d.name.as_str());
+
+        assert_eq!(name("/bin/run", "#!/bin/sh\necho"), Some("shell"));
+        assert_eq!(
+            name("/bin/run", "#!/usr/bin/env -S node --no-warnings\n"),
+            Some("javascript")
+        );
+        assert_eq!(name("/bin/run", "echo"), None);
+        // the extension wins over the shebang
+        assert_eq!(name("/bin/run.txt", "#!/bin/sh"), None);
+    }
+
+    #[test]
+    fn test_config_formats() {
+        let config = r##"
+[[formats]]
+name = "terraform"
+extensions = ["tf"]
+line_comments = ["#", "//"]
+block_comments = [["/*", "*/"]]
+quotes = ['"']
+
+[[formats]]
+name = "rust"
+globs = ["**/legacy/*.rs"]
+line_comments = ["//"]
+"##;
+        #[derive(serde::Deserialize)]
+        struct Config {
+            formats: Vec<Definition>,
+        }
+        let config: Config = toml::from_str(config).unwrap();
+        let formats = Formats::new(config.formats);
+
+        let terraform = formats.detect("/infra/main.tf", "").unwrap();
+        assert_eq!(terraform.name, "terraform");
+        assert_eq!(
+            terraform.syntax,
+            Syntax::new(&["#", "//"], &[("/*", "*/")], &['"'])
+        );
+
+        // the built-in rust format is replaced by the one of the config
+        assert!(formats.detect("/src/main.rs", "").is_none());
+        let rust = formats.detect("/src/legacy/main.rs", "").unwrap();
+        assert!(rust.syntax.block.is_empty());
+
+        assert_eq!(formats.detect("/src/main.c", "").unwrap().name, "c");
     }
 }
@@ -0,0 +1,239 @@
+/// defaults for every task and todo of a file read from the `owl` key of its YAML (`---`) or
+/// TOML (`+++`) front matter
+///
+/// ```markdown
+/// ---
+/// title: Thesis
+/// owl:
+///   prefix: Uni/Thesis
+///   tags: [writing, uni]
+///   assignee: alice
+///   skip_todos: true
+/// ---
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FrontMatter {
+    pub prefix: Option<String>,
+    pub tags: Vec<String>,
+    pub assignees: Vec<String>,
+    pub skip_tasks: bool,
+    pub skip_todos: bool,
+}
+
+impl FrontMatter {
+    fn set(&mut self, key: &str, values: Vec<String>) {
+        let is_true = |values: &[String]| values.first().is_some_and(|v| v == "true");
+        match key {
+            "prefix" => self.prefix = values.into_iter().next(),
+            "tags" => self.tags = values,
+            "assignee" | "assignees" => self.assignees = values,
+            "skip_tasks" => self.skip_tasks = is_true(&values),
+            "skip_todos" => self.skip_todos = is_true(&values),
+            _ => log::warn!("ignoring unknown front matter key: owl.{}", key),
+        }
+    }
+
+    /// reads the front matter at the start of `body`
+    ///
+    /// # Returns
+    /// the front matter and the number of lines it spans, files without front matter return the
+    /// default front matter and `0`
+    ///
+    /// # Example
+    /// ```
+    /// use owl::frontmatter::FrontMatter;
+    /// let body = "+++\n[owl]\nprefix = \"Uni\"\nskip_todos = true\n+++\n# TASK: Exam";
+    ///
+    /// let (front_matter, lines) = FrontMatter::parse(body);
+    /// assert_eq!(front_matter.prefix.as_deref(), Some("Uni"));
+    /// assert!(front_matter.skip_todos);
+    /// assert_eq!(lines, 5);
+    /// ```
+    ///
+    /// # Errors
+    /// erroniously formatted front matter is ignored
+    pub fn parse(body: &str) -> (Self, usize) {
+        let mut lines = body.lines();
+        let delimiter = match lines.next().map(|l| l.trim_end()) {
+            Some("---") => "---",
+            Some("+++") => "+++",
+            _ => return (Self::default(), 0),
+        };
+
+        let Some(len) = lines.position(|l| {
+            let l = l.trim_end();
+            l == delimiter || (delimiter == "---" && l == "...")
+        }) else {
+            return (Self::default(), 0);
+        };
+
+        let block = body.lines().skip(1).take(len).collect::<Vec<_>>();
+        let front_matter = if delimiter == "---" {
+            Self::from_yaml(&block)
+        } else {
+            Self::from_toml(&block.join("\n"))
+        };
+
+        (front_matter, len + 2)
+    }
+
+    /// reads the `owl` mapping of a YAML document, only plain scalars, flow lists (`[a, b]`) and
+    /// block lists (`- a`) are supported
+    fn from_yaml(lines: &[&str]) -> Self {
+        let mut front_matter = Self::default();
+
+        let Some(start) = lines.iter().position(|l| l.trim_end() == "owl:") else {
+            return front_matter;
+        };
+
+        let mut key: Option<&str> = None;
+        let mut values = Vec::new();
+
+        for line in lines[start + 1..].iter() {
+            let content = line.split(" #").next().unwrap_or("").trim();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+            // the `owl` mapping ends at the next top level key
+            if !line.starts_with(char::is_whitespace) {
+                break;
+            }
+
+            if let Some(item) = content.strip_prefix("- ") {
+                values.push(yaml_scalar(item));
+                continue;
+            }
+
+            let Some((k, value)) = content.split_once(':') else {
+                continue;
+            };
+            if let Some(key) = key.take() {
+                front_matter.set(key, std::mem::take(&mut values));
+            }
+            key = Some(k.trim());
+
+            let value = value.trim();
+            if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                values.extend(list.split(',').map(yaml_scalar).filter(|v| !v.is_empty()));
+            } else if !value.is_empty() {
+                values.push(yaml_scalar(value));
+            }
+        }
+
+        if let Some(key) = key {
+            front_matter.set(key, values);
+        }
+
+        front_matter
+    }
+
+    fn from_toml(block: &str) -> Self {
+        let mut front_matter = Self::default();
+
+        let table = match block.parse::<toml::Table>() {
+            Ok(table) => table,
+            Err(err) => {
+                log::warn!("ignoring front matter because: {:?}", err);
+                return front_matter;
+            }
+        };
+
+        let Some(toml::Value::Table(owl)) = table.get("owl") else {
+            return front_matter;
+        };
+
+        let scalar = |value: &toml::Value| match value {
+            toml::Value::String(s) => s.clone(),
+            value => value.to_string(),
+        };
+
+        for (key, value) in owl.iter() {
+            let values = match value {
+                toml::Value::Array(values) => values.iter().map(scalar).collect(),
+                value => vec![scalar(value)],
+            };
+            front_matter.set(key, values);
+        }
+
+        front_matter
+    }
+}
+
+fn yaml_scalar(value: &str) -> String {
+    let value = value.trim();
+    let quoted = value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')));
+    if quoted {
+        value[1..value.len() - 1].into()
+    } else {
+        value.into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_yaml() {
+        let body = "---
+title: Thesis
+owl:
+  prefix: \"Uni/Thesis\"
+  # tags of every task
+  tags: [writing, uni]
+  assignee:
+    - alice
+    - bob
+  skip_todos: true
+date: 2025-07-01
+---
+# TASK: Write
+";
+        let expected = FrontMatter {
+            prefix: Some("Uni/Thesis".into()),
+            tags: vec!["writing".into(), "uni".into()],
+            assignees: vec!["alice".into(), "bob".into()],
+            skip_tasks: false,
+            skip_todos: true,
+        };
+
+        assert_eq!(FrontMatter::parse(body), (expected, 12));
+    }
+
+    #[test]
+    fn test_parse_toml() {
+        let body = "+++
+title = \"Thesis\"
+
+[owl]
+prefix = \"Uni/Thesis\"
+tags = [\"writing\"]
+assignee = \"alice\"
+skip_tasks = true
++++
+";
+        let expected = FrontMatter {
+            prefix: Some("Uni/Thesis".into()),
+            tags: vec!["writing".into()],
+            assignees: vec!["alice".into()],
+            skip_tasks: true,
+            skip_todos: false,
+        };
+
+        assert_eq!(FrontMatter::parse(body), (expected, 9));
+    }
+
+    #[test]
+    fn test_parse_without_front_matter() {
+        assert_eq!(
+            FrontMatter::parse("# Uni\n---\n"),
+            (FrontMatter::default(), 0)
+        );
+        assert_eq!(
+            FrontMatter::parse("---\nowl:\n  skip_tasks: true\n"),
+            (FrontMatter::default(), 0)
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use crate::time::Date;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// who committed a line last and when
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blame {
+    pub author: String,
+    /// the author date of the commit in the timezone of the author
+    pub date: Date,
+}
+
+/// the hash git blame reports for lines which have not been committed yet
+const UNCOMMITTED: &str = "0000000000000000000000000000000000000000";
+
+/// converts a unix timestamp and a timezone like `+0200` to the date in that timezone
+fn date(timestamp: &str, timezone: &str) -> Option<Date> {
+    let timestamp = timestamp.parse::<i64>().ok()?;
+    let sign = match timezone.get(..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let hours = timezone.get(1..3)?.parse::<i32>().ok()?;
+    let minutes = timezone.get(3..5)?.parse::<i32>().ok()?;
+    let offset = chrono::FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))?;
+    let date = chrono::DateTime::from_timestamp(timestamp, 0)?
+        .with_timezone(&offset)
+        .date_naive();
+    Some(Date::from_naive_date(date))
+}
+
+/// parses the output of `git blame --line-porcelain`, the blame of line `n` is at index `n - 1`
+/// and is `None` if the line has not been committed yet
+fn parse_porcelain(output: &str) -> Vec<Option<Blame>> {
+    let mut blames = Vec::new();
+
+    let mut commit = "";
+    let mut author = "";
+    let mut time = "";
+    let mut timezone = "";
+
+    for line in output.lines() {
+        // the content of the line ends the entry
+        if line.starts_with('\t') {
+            let blame = (commit != UNCOMMITTED)
+                .then(|| date(time, timezone))
+                .flatten()
+                .map(|date| Blame {
+                    author: author.to_string(),
+                    date,
+                });
+            blames.push(blame);
+            commit = "";
+            continue;
+        }
+
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "author" => author = value,
+            "author-time" => time = value,
+            "author-tz" => timezone = value,
+            _ if commit.is_empty() => commit = key,
+            _ => (),
+        }
+    }
+
+    blames
+}
+
+/// runs git with `args` in `directory` and returns its output
+///
+/// # Errors
+/// returns the error message if git could not be run or failed
+fn run<I, S>(directory: &Path, args: I) -> Result<String, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        }
+        Ok(output) => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+        Err(err) => Err(format!("could not run git because: {:?}", err)),
+    }
+}
+
+/// runs `git blame` on the file at `path`
+///
+/// # Errors
+/// returns `None` and logs the reason if git is not installed, the file is not inside a
+/// repository or not tracked
+pub fn blame(path: &Path) -> Option<Vec<Option<Blame>>> {
+    let directory = path.parent()?;
+    let name = path.file_name()?;
+    match run(
+        directory,
+        [
+            OsStr::new("blame"),
+            "--line-porcelain".as_ref(),
+            "--".as_ref(),
+            name,
+        ],
+    ) {
+        Ok(output) => Some(parse_porcelain(&output)),
+        Err(err) => {
+            log::info!("not blaming: {:?} because: {}", path, err);
+            None
+        }
+    }
+}
+
+/// returns the root directory of the repository `directory` is in
+///
+/// # Errors
+/// returns the message of git if `directory` is not inside a repository
+pub fn toplevel(directory: &Path) -> Result<PathBuf, String> {
+    run(directory, ["rev-parse", "--show-toplevel"]).map(|output| output.trim_end().into())
+}
+
+/// returns the paths relative to the root of the repository of the files changed between the
+/// revisions `base` and `head`, an empty `head` stands for the index
+///
+/// # Errors
+/// returns the message of git if a revision does not exist
+pub fn changed_files(directory: &Path, base: &str, head: &str) -> Result<Vec<PathBuf>, String> {
+    let mut args = vec!["diff", "--name-only", "--no-renames", "-z"];
+    if head.is_empty() {
+        args.extend(["--cached", base]);
+    } else {
+        args.extend([base, head]);
+    }
+    args.push("--");
+
+    let output = run(directory, args)?;
+    Ok(output
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// returns the best common ancestor of the revisions `a` and `b` as used by ranges like `a...b`
+///
+/// # Errors
+/// returns the message of git if a revision does not exist or they have no common ancestor
+pub fn merge_base(directory: &Path, a: &str, b: &str) -> Result<String, String> {
+    run(directory, ["merge-base", a, b]).map(|output| output.trim_end().to_string())
+}
+
+/// returns the content of the file at `path` relative to the root of the repository in
+/// `revision`, an empty `revision` stands for the index
+///
+/// returns `None` if the file does not exist in `revision`
+pub fn show(directory: &Path, revision: &str, path: &Path) -> Option<String> {
+    let object = format!("{}:{}", revision, path.to_string_lossy());
+    run(directory, ["show", object.as_str()]).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn test_parse_porcelain() {
+        let output = "\
+4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d0e1f2a3b 1 1 2
+author Alice
+author-mail <alice@example.com>
+author-time 1700000000
+author-tz +0100
+committer Alice
+summary add main
+filename main.rs
+\tfn main() {
+4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d0e1f2a3b 2 2
+author Alice
+author-mail <alice@example.com>
+author-time 1700000000
+author-tz -1000
+summary add main
+filename main.rs
+\t    // TODO: greet
+0000000000000000000000000000000000000000 3 3 1
+author Not Committed Yet
+author-mail <not.committed.yet>
+author-time 1710000000
+author-tz +0000
+summary Version of main.rs from main.rs
+filename main.rs
+\t}
+";
+        let blames = parse_porcelain(output);
+        assert_eq!(
+            blames,
+            vec![
+                Some(Blame {
+                    author: "Alice".into(),
+                    date: Date::from_ymd(2023, 11, 14).unwrap(),
+                }),
+                Some(Blame {
+                    author: "Alice".into(),
+                    date: Date::from_ymd(2023, 11, 14).unwrap(),
+                }),
+                None,
+            ]
+        );
+        assert_eq!(
+            date("1700000000", "+1000"),
+            Some(Date::from_ymd(2023, 11, 15).unwrap())
+        );
+    }
+
+    /// creates an empty repository in a temporary directory and returns it with a function
+    /// which runs git in it
+    fn repository(name: &str) -> (PathBuf, impl Fn(&[&str])) {
+        let directory =
+            std::env::temp_dir().join(format!("owl-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let git = {
+            let directory = directory.clone();
+            move |args: &[&str]| {
+                let status = Command::new("git")
+                    .arg("-C")
+                    .arg(&directory)
+                    .args(args)
+                    .env("GIT_AUTHOR_NAME", "Alice")
+                    .env("GIT_AUTHOR_EMAIL", "alice@example.com")
+                    .env("GIT_AUTHOR_DATE", "2020-05-17T12:00:00+00:00")
+                    .env("GIT_COMMITTER_NAME", "Alice")
+                    .env("GIT_COMMITTER_EMAIL", "alice@example.com")
+                    .status()
+                    .unwrap();
+                assert!(status.success());
+            }
+        };
+        git(&["init", "-q"]);
+        (directory, git)
+    }
+
+    #[test]
+    fn test_blame() {
+        let (directory, git) = repository("blame");
+
+        let path = directory.join("main.rs");
+        std::fs::write(&path, "// TODO: old\n").unwrap();
+        git(&["add", "main.rs"]);
+        git(&["commit", "-q", "-m", "add main"]);
+        std::fs::write(&path, "// TODO: old\n// TODO: new\n").unwrap();
+
+        let blames = blame(&path).unwrap();
+        assert_eq!(
+            blames,
+            vec![
+                Some(Blame {
+                    author: "Alice".into(),
+                    date: Date::from_ymd(2020, 5, 17).unwrap(),
+                }),
+                None,
+            ]
+        );
+
+        let untracked = directory.join("untracked.rs");
+        std::fs::write(&untracked, "// TODO: untracked\n").unwrap();
+        assert_eq!(blame(&untracked), None);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_changed_files() {
+        let (directory, git) = repository("diff");
+
+        std::fs::create_dir(directory.join("src")).unwrap();
+        std::fs::write(directory.join("src/main.rs"), "// TODO: old\n").unwrap();
+        std::fs::write(directory.join("README.md"), "# owl\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "first"]);
+        std::fs::write(directory.join("src/main.rs"), "// TODO: new\n").unwrap();
+        std::fs::write(directory.join("lib.rs"), "// TODO: staged\n").unwrap();
+        git(&["commit", "-q", "-am", "second"]);
+        git(&["add", "lib.rs"]);
+
+        let subdirectory = directory.join("src");
+        assert_eq!(
+            toplevel(&subdirectory).unwrap().canonicalize().unwrap(),
+            directory.canonicalize().unwrap()
+        );
+        assert_eq!(
+            changed_files(&subdirectory, "HEAD~", "HEAD").unwrap(),
+            vec![PathBuf::from("src/main.rs")]
+        );
+        assert_eq!(
+            changed_files(&subdirectory, "HEAD", "").unwrap(),
+            vec![PathBuf::from("lib.rs")]
+        );
+        assert!(changed_files(&directory, "nope", "HEAD").is_err());
+
+        let path = Path::new("src/main.rs");
+        assert_eq!(
+            show(&directory, "HEAD~", path).as_deref(),
+            Some("// TODO: old\n")
+        );
+        assert_eq!(
+            show(&directory, "", Path::new("lib.rs")).as_deref(),
+            Some("// TODO: staged\n")
+        );
+        assert_eq!(show(&directory, "HEAD", Path::new("lib.rs")), None);
+
+        git(&["tag", "second"]);
+        git(&["reset", "-q", "lib.rs"]);
+        git(&["checkout", "-q", "-b", "side", "HEAD~"]);
+        std::fs::write(directory.join("README.md"), "# side\n").unwrap();
+        git(&["commit", "-q", "-am", "side"]);
+        let base = merge_base(&directory, "second", "side").unwrap();
+        assert_eq!(
+            changed_files(&directory, &base, "side").unwrap(),
+            vec![PathBuf::from("README.md")]
+        );
+        assert!(merge_base(&directory, "nope", "side").is_err());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
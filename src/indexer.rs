@@ -1,13 +1,21 @@
 use std::{
     collections::{HashMap, HashSet},
     path::Path,
+    time::{Duration, SystemTime},
 };
 
-use crate::{config::Config, file::File, format::Format, store::Store, task, todo};
+use crate::{config::Config, event, file::File, git, store::Store, task, todo};
+
+/// how long after its last change a file may change again without getting a newer mtime, because
+/// file systems only update mtimes once per tick of a coarse clock
+const MTIME_GRANULARITY: Duration = Duration::from_secs(2);
 
 /// recursively discoveres every file starting from `config.base_directory` checks if it needs to
 /// be reparsed based on the `mtime` stored in the associated `store.files` and updates
-/// `store.tasks` and `store.todos` accordingly
+/// `store.tasks`, `store.events` and `store.todos` accordingly
+///
+/// every file is reparsed if the settings of the parsers in `config` have changed since the last
+/// scan
 ///
 /// # Panics
 /// - function panics if `config.base_directory` is not a valid path to a directory
@@ -20,16 +28,24 @@ pub fn index(store: &mut Store, config: &Config) {
         );
     }
 
-    let mut mtime_map = store
+    let fingerprint = config.fingerprint();
+    if store.fingerprint != fingerprint {
+        log::info!("reparsing every file because the config has changed since last scan");
+        store.files.clear();
+        store.fingerprint = fingerprint;
+    }
+
+    let mtime_map = store
         .files
-        .iter_mut()
-        .map(|file| (file.path.as_path(), &mut file.mtime))
+        .iter()
+        .map(|file| (file.path.as_path(), file))
         .collect::<HashMap<_, _>>();
 
     let mut directories = vec![config.base_directory.clone()];
     let mut files = vec![];
-
-    let now = std::time::SystemTime::now();
+    // files which have not changed since the last scan, every other file is dropped from the
+    // store and reparsed if it still exists
+    let mut unchanged = HashSet::new();
 
     while let Some(directory) = directories.pop() {
         let read_dir = match std::fs::read_dir(&directory) {
@@ -84,7 +100,7 @@ pub fn index(store: &mut Store, config: &Config) {
                 continue 'entry_loop;
             }
 
-            if Format::new(&path).is_unknown() {
+            if config.formats.detect_file(&path).is_none() {
                 log::info!("ignoring file: {:?} because format is unknown", path);
                 continue 'entry_loop;
             }
@@ -105,43 +121,32 @@ pub fn index(store: &mut Store, config: &Config) {
             // we don't support such platforms
             let mtime = mtime.modified().unwrap();
 
-            if let Some(last_mtime) = mtime_map.get_mut(path.as_path()) {
-                if **last_mtime >= mtime {
-                    log::info!(
-                        "ignoring: file {:?} because it has not changed since last scan",
-                        path
-                    );
-                    **last_mtime = now;
-                    continue;
-                }
+            // files with todos which have not been committed yet are blamed again
+            if let Some(file) = mtime_map.get(path.as_path())
+                && file.mtime >= mtime
+                && !file.reparse
+                && (file.blamed || !config.todo_blame)
+            {
+                log::info!(
+                    "ignoring: file {:?} because it has not changed since last scan",
+                    path
+                );
+                unchanged.insert(path);
+                continue;
             }
 
             files.push((path, mtime));
         }
     }
 
-    // remove files from store which will be updated
-
-    let file_set = files
-        .iter()
-        .map(|(path, _)| path.as_path())
-        .collect::<HashSet<_>>();
-
-    let to_remove = store
-        .files
-        .iter()
-        .filter(|file| file.mtime < now)
-        .map(|file| file.path.as_path())
-        .collect::<HashSet<_>>();
-
-    let retain = |path: &Path| !file_set.contains(path) && !to_remove.contains(path);
+    // remove files from store which will be updated or have been deleted
 
-    store.tasks.retain(|task| retain(task.path.as_path()));
-    store.todos.retain(|todo| retain(todo.path.as_path()));
+    store.tasks.retain(|task| unchanged.contains(&task.path));
+    store.todos.retain(|todo| unchanged.contains(&todo.path));
+    store.events.retain(|event| unchanged.contains(&event.path));
+    store.files.retain(|file| unchanged.contains(&file.path));
 
-    store
-        .files
-        .retain(|file| !file_set.contains(file.path.as_path()) && file.mtime >= now);
+    let todo_options = config.todo_options();
 
     for (path, mtime) in files.into_iter() {
         let body = match std::fs::read_to_string(&path) {
@@ -151,12 +156,240 @@ pub fn index(store: &mut Store, config: &Config) {
                 continue;
             }
         };
+        // a file which changed shortly before it was read may be written again with the same
+        // mtime, so it is parsed again by the next scan
+        let reparse = SystemTime::now()
+            .duration_since(mtime)
+            .map_or(true, |age| age < MTIME_GRANULARITY);
 
-        let todos = todo::parse(&body, &path).into_iter();
-        let tasks = task::Task::parse(&body, &path).into_iter();
+        let mut todos = todo::parse_with(&body, &path, &todo_options);
+        let blamed = config.todo_blame && blame(&mut todos, &path);
+        let tasks = task::Task::parse_with(&body, &path, &config.formats)
+            .into_iter()
+            .map(|mut task| {
+                task.truncate_body(config.max_task_body);
+                task
+            });
+        let events = event::Event::parse_with(&body, &path, &config.formats).into_iter();
 
         store.todos.extend(todos);
         store.tasks.extend(tasks);
-        store.files.push(File { path, mtime });
+        store.events.extend(events);
+        store.files.push(File {
+            path,
+            mtime,
+            blamed,
+            reparse,
+        });
+    }
+}
+
+/// sets the author and commit date of `todos` by running `git blame` on the file at `path` and
+/// returns whether every todo has been blamed
+///
+/// files outside of repositories count as blamed, todos in notebooks are not blamed because
+/// their line numbers count from the start of their cell
+fn blame(todos: &mut [todo::Todo], path: &Path) -> bool {
+    if todos.iter().all(|todo| todo.cell.is_some()) {
+        return true;
+    }
+    let Some(blames) = git::blame(path) else {
+        return true;
+    };
+
+    let mut blamed = true;
+    for todo in todos.iter_mut().filter(|todo| todo.cell.is_none()) {
+        match blames.get(todo.line_number - 1).cloned().flatten() {
+            Some(blame) => {
+                todo.author = Some(blame.author);
+                todo.committed = Some(blame.date);
+            }
+            None => blamed = false,
+        }
+    }
+    blamed
+}
+
+/// prepends the directory of every task and event to its prefix as configured by
+/// `config.directory_prefix`, tasks remember the directory so that references written without it
+/// still match
+///
+/// this is not part of `index` because the store keeps the prefixes as written in the files,
+/// therefore it has to be applied after the store has been saved
+pub fn apply_directory_prefix(store: &mut Store, config: &Config) {
+    let prefix = |path: &Path, prefix: &mut String| {
+        let directory = config
+            .directory_prefix
+            .prefix(&config.base_directory, path)?;
+        *prefix = if prefix.is_empty() {
+            directory.clone()
+        } else {
+            format!("{}/{}", directory, prefix)
+        };
+        Some(directory)
+    };
+
+    for task in store.tasks.iter_mut() {
+        task.directory = prefix(&task.path, &mut task.prefix);
+    }
+    for event in store.events.iter_mut() {
+        prefix(&event.path, &mut event.prefix);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DirectoryPrefix;
+    use crate::format::Formats;
+    use crate::policy::Policy;
+    use std::path::PathBuf;
+
+    /// creates an empty temporary directory
+    fn directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("owl-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    /// returns the default config which scans `base_directory`
+    fn config(base_directory: PathBuf) -> Config {
+        Config {
+            ignore_hidden_files: true,
+            ignore: Vec::new(),
+            base_directory,
+            me: None,
+            max_task_body: 4096,
+            open_command: String::new(),
+            directory_prefix: DirectoryPrefix::None,
+            todo_markers: todo::default_markers(),
+            todo_inline: false,
+            todo_blame: false,
+            todo_policy: Policy::default(),
+            formats: Formats::default(),
+        }
+    }
+
+    fn titles(store: &Store) -> Vec<&str> {
+        let mut titles = store
+            .todos
+            .iter()
+            .map(|todo| todo.title.as_str())
+            .collect::<Vec<_>>();
+        titles.sort();
+        titles
+    }
+
+    #[test]
+    fn test_apply_directory_prefix() {
+        let mut config = config("/home/user".into());
+        config.directory_prefix = DirectoryPrefix::Path;
+
+        let body = r#"# Release
+## TASK: Write docs
+> DEADLINE: 2025-07-01
+## TASK: Publish
+> BLOCKED_BY: Release/Write docs
+> SCHEDULED: after(projects/owl/Release/Write docs) +1d
+## EVENT: Launch
+"#;
+        let path = "/home/user/projects/owl/plan.md";
+        let mut store = Store {
+            tasks: task::Task::parse(body, path),
+            events: event::Event::parse(body, path),
+            ..Default::default()
+        };
+        store.tasks.extend(task::Task::parse(
+            "# TASK: Top level",
+            "/home/user/inbox.md",
+        ));
+        apply_directory_prefix(&mut store, &config);
+
+        let paths = store
+            .tasks
+            .iter()
+            .map(|task| task.heading_path())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "projects/owl/Release/Write docs",
+                "projects/owl/Release/Publish",
+                "Top level",
+            ]
+        );
+        assert_eq!(store.events[0].prefix, "projects/owl/Release");
+
+        // references with and without the directory resolve
+        let graph = crate::deps::Graph::new(&store.tasks);
+        assert_eq!(graph.blockers[1], vec![0]);
+        crate::schedule::resolve(&mut store);
+        assert!(store.diagnostics.is_empty());
+        assert_eq!(
+            store.tasks[1].scheduled,
+            Some("2025-07-02".parse().unwrap())
+        );
+    }
+
+    /// writes `body` to the file at `path` and sets its mtime to `mtime`
+    fn write(path: &Path, body: &str, mtime: SystemTime) {
+        std::fs::write(path, body).unwrap();
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(mtime).unwrap();
+    }
+
+    #[test]
+    fn test_index_write_after_scan() {
+        let config = config(directory("index-write"));
+        let path = config.base_directory.join("main.rs");
+        let mut store = Store::default();
+
+        // the mtime of a file written after a scan can be older than the time of the scan
+        let old = SystemTime::now() - Duration::from_secs(60);
+        write(&path, "// TODO: first\n", old);
+        index(&mut store, &config);
+        index(&mut store, &config);
+        write(&path, "// TODO: second\n", old + Duration::from_secs(1));
+        index(&mut store, &config);
+        assert_eq!(titles(&store), vec!["second"]);
+
+        // a file written in the same tick as it was read keeps its mtime
+        let now = SystemTime::now();
+        write(&path, "// TODO: third\n", now);
+        index(&mut store, &config);
+        assert_eq!(titles(&store), vec!["third"]);
+        let mtime = std::fs::metadata(&path).unwrap().modified().unwrap();
+        assert_eq!(store.files[0].mtime, mtime);
+        assert!(store.files[0].reparse);
+        write(&path, "// TODO: fourth\n", now);
+        index(&mut store, &config);
+        assert_eq!(titles(&store), vec!["fourth"]);
+
+        std::fs::remove_dir_all(&config.base_directory).unwrap();
+    }
+
+    #[test]
+    fn test_index_config_changed() {
+        let mut config = config(directory("index-config"));
+        let path = config.base_directory.join("main.rs");
+        std::fs::write(&path, "// TODO: fix this\n// FIXME: and this\n").unwrap();
+
+        let mut store = Store::default();
+        index(&mut store, &config);
+        assert_eq!(titles(&store), vec!["and this", "fix this"]);
+
+        config.todo_markers = vec![todo::Marker::new("FIXME", todo::Severity::Error)];
+        index(&mut store, &config);
+        assert_eq!(titles(&store), vec!["and this"]);
+        assert_eq!(store.files.len(), 1);
+
+        // turning blame off drops the blame of cached todos
+        let fingerprint = config.fingerprint();
+        config.todo_blame = true;
+        assert_ne!(config.fingerprint(), fingerprint);
+
+        std::fs::remove_dir_all(&config.base_directory).unwrap();
     }
 }
@@ -2,11 +2,22 @@ pub mod config;
 pub mod indexer;
 pub mod error;
 pub mod task;
+pub mod event;
 pub mod context;
 pub mod time;
 pub mod todo;
+pub mod comment;
+pub mod notebook;
+pub mod symbol;
+pub mod git;
+pub mod policy;
+pub mod report;
+pub mod promote;
 pub mod format;
+pub mod frontmatter;
 pub mod store;
 pub mod file;
 pub mod cli;
 pub mod tesc;
+pub mod deps;
+pub mod schedule;
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use clap::Parser;
-use owl::{cli, config::Config, indexer, store::Store};
+use owl::{cli, config::Config, indexer, schedule, store::Store};
 
 fn logger_init() {
     let level = if let Ok(level) = std::env::var("LOG_LEVEL") {
@@ -47,10 +47,19 @@ fn main() {
 
     log::info!("scanned directories");
 
+    // the store is saved before prefixes and relative dates are applied because they depend on
+    // the config and on other files
+    path.push("store.json");
+    store.close(&path);
+    path.pop();
+
+    indexer::apply_directory_prefix(&mut store, &config);
+
+    schedule::resolve(&mut store);
+
+    log::info!("resolved relative dates");
+
     let args = cli::Args::parse();
 
     cli::run(&config, &store, &args);
-
-    path.push("store.json");
-    store.close(&path);
 }
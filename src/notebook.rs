@@ -0,0 +1,174 @@
+/// the type of a notebook cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CellType {
+    Markdown,
+    Code,
+    Raw,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub cell_type: CellType,
+    pub source: String,
+}
+
+/// a Jupyter notebook (`.ipynb`), only the parts owl needs are read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notebook {
+    /// language of the kernel, eg. `python`
+    pub language: Option<String>,
+    pub cells: Vec<Cell>,
+}
+
+#[derive(serde::Deserialize)]
+struct NotebookRaw {
+    #[serde(default)]
+    cells: Vec<CellRaw>,
+    #[serde(default)]
+    metadata: MetadataRaw,
+}
+
+#[derive(serde::Deserialize)]
+struct CellRaw {
+    cell_type: CellType,
+    #[serde(default)]
+    source: SourceRaw,
+}
+
+/// the source of a cell is either a string or a list of lines which keep their `\n`
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum SourceRaw {
+    Text(String),
+    Lines(Vec<String>),
+}
+
+impl Default for SourceRaw {
+    fn default() -> Self {
+        Self::Text(String::new())
+    }
+}
+
+#[derive(Default, serde::Deserialize)]
+struct MetadataRaw {
+    kernelspec: Option<KernelSpecRaw>,
+    language_info: Option<LanguageInfoRaw>,
+}
+
+#[derive(serde::Deserialize)]
+struct KernelSpecRaw {
+    language: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct LanguageInfoRaw {
+    name: Option<String>,
+}
+
+impl Notebook {
+    /// reads a notebook from its JSON `body`
+    ///
+    /// # Example
+    /// ```
+    /// use owl::notebook::{CellType, Notebook};
+    /// let body = r##"{
+    ///     "cells": [{ "cell_type": "code", "source": ["# TODO: plot\n", "x = 1"] }],
+    ///     "metadata": { "kernelspec": { "name": "python3", "language": "python" } }
+    /// }"##;
+    ///
+    /// let notebook = Notebook::parse(body).unwrap();
+    /// assert_eq!(notebook.language.as_deref(), Some("python"));
+    /// assert_eq!(notebook.cells[0].cell_type, CellType::Code);
+    /// assert_eq!(notebook.cells[0].source, "# TODO: plot\nx = 1");
+    /// ```
+    ///
+    /// # Errors
+    /// returns `None` and logs a warning if `body` is not a notebook
+    pub fn parse(body: &str) -> Option<Self> {
+        let notebook: NotebookRaw = match serde_json::from_str(body) {
+            Ok(notebook) => notebook,
+            Err(err) => {
+                log::warn!("ignoring notebook because: {:?}", err);
+                return None;
+            }
+        };
+
+        let language = notebook
+            .metadata
+            .kernelspec
+            .and_then(|kernel| kernel.language)
+            .or(notebook.metadata.language_info.and_then(|info| info.name))
+            .map(|language| language.to_lowercase());
+
+        let cells = notebook
+            .cells
+            .into_iter()
+            .map(|cell| Cell {
+                cell_type: cell.cell_type,
+                source: match cell.source {
+                    SourceRaw::Text(text) => text,
+                    SourceRaw::Lines(lines) => lines.concat(),
+                },
+            })
+            .collect();
+
+        Some(Self { language, cells })
+    }
+
+    /// returns the cells of type `cell_type` with their index starting at `1`
+    pub fn cells(&self, cell_type: CellType) -> impl Iterator<Item = (usize, &Cell)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (i + 1, cell))
+            .filter(move |(_, cell)| cell.cell_type == cell_type)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let body = r##"{
+  "cells": [
+    { "cell_type": "markdown", "metadata": {}, "source": "# Analysis\n## TASK: clean data" },
+    { "cell_type": "code", "execution_count": 1, "outputs": [], "source": [] },
+    { "cell_type": "raw", "source": ["raw"] }
+  ],
+  "metadata": { "language_info": { "name": "Python", "version": "3.12" } },
+  "nbformat": 4,
+  "nbformat_minor": 5
+}"##;
+        let notebook = Notebook::parse(body).unwrap();
+
+        assert_eq!(notebook.language.as_deref(), Some("python"));
+        assert_eq!(
+            notebook.cells,
+            vec![
+                Cell {
+                    cell_type: CellType::Markdown,
+                    source: "# Analysis\n## TASK: clean data".into(),
+                },
+                Cell {
+                    cell_type: CellType::Code,
+                    source: String::new(),
+                },
+                Cell {
+                    cell_type: CellType::Raw,
+                    source: "raw".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            notebook
+                .cells(CellType::Code)
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert!(Notebook::parse("not json").is_none());
+    }
+}
@@ -0,0 +1,266 @@
+use crate::time::Date;
+use crate::todo::{Severity, Todo};
+use std::fmt::Display;
+use std::path::Path;
+
+/// rules todos have to follow, they are configured in the `[todo_policy]` table of the config and
+/// checked by `owl todo check`
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct Policy {
+    /// every todo needs an owner like `TODO(alice): ...`
+    pub require_owner: bool,
+    /// every todo needs an issue like `TODO(#123): ...`
+    pub require_issue: bool,
+    /// the lowest severity of the todos which `require_owner` and `require_issue` apply to, by
+    /// default they apply to every marker
+    pub min_severity: Option<Severity>,
+    /// todos must not be past their due date
+    pub forbid_overdue: bool,
+    /// the maximum number of todos in a single file
+    pub max_per_file: Option<usize>,
+    /// markers which are not allowed in some files
+    pub forbid: Vec<Forbid>,
+}
+
+/// forbids `markers` in the files matching `globs`, or in every file if there are no `globs`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Forbid {
+    pub markers: Vec<String>,
+    #[serde(default)]
+    pub globs: Vec<String>,
+}
+
+impl Forbid {
+    fn matches(&self, todo: &Todo) -> bool {
+        let path = todo.path.as_os_str().as_encoded_bytes();
+        self.markers.contains(&todo.kind)
+            && (self.globs.is_empty()
+                || self
+                    .globs
+                    .iter()
+                    .any(|glob| fast_glob::glob_match(glob.as_bytes(), path)))
+    }
+}
+
+/// the rule of a policy which a todo breaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    MissingOwner,
+    MissingIssue,
+    Overdue,
+    ForbiddenMarker,
+    TooManyTodos,
+}
+
+impl Rule {
+    /// the identifier of the rule as printed by `owl todo check`
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::MissingOwner => "missing-owner",
+            Rule::MissingIssue => "missing-issue",
+            Rule::Overdue => "overdue",
+            Rule::ForbiddenMarker => "forbidden-marker",
+            Rule::TooManyTodos => "too-many-todos",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation<'a> {
+    pub todo: &'a Todo,
+    pub rule: Rule,
+    pub message: String,
+}
+
+impl Display for Violation<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {}: {}",
+            self.todo.location(),
+            self.rule.id(),
+            self.message
+        )
+    }
+}
+
+/// checks `todos` against the rules of `policy` and returns the violations ordered by their
+/// location, `today` decides which todos are overdue and `severity` returns the severity of a
+/// marker
+///
+/// # Example
+/// ```
+/// use owl::policy::{Policy, Rule, check};
+/// use owl::time::Date;
+/// use owl::todo::{Severity, parse};
+/// let todos = parse("// TODO(alice): fine\n// TODO: whose?\n", "main.rs");
+/// let policy = Policy {
+///     require_owner: true,
+///     ..Default::default()
+/// };
+///
+/// let violations = check(&todos, &policy, Date::today(), |_| Severity::Warning);
+/// assert_eq!(violations.len(), 1);
+/// assert_eq!(violations[0].rule, Rule::MissingOwner);
+/// assert_eq!(violations[0].todo.line_number, 2);
+/// ```
+pub fn check<'a>(
+    todos: &'a [Todo],
+    policy: &Policy,
+    today: Date,
+    severity: impl Fn(&str) -> Severity,
+) -> Vec<Violation<'a>> {
+    let mut violations = Vec::new();
+    let mut violate = |todo, rule, message| {
+        violations.push(Violation {
+            todo,
+            rule,
+            message,
+        })
+    };
+
+    for todo in todos {
+        let required = policy
+            .min_severity
+            .is_none_or(|min_severity| severity(&todo.kind) >= min_severity);
+        if required && policy.require_owner && todo.owner.is_none() {
+            violate(
+                todo,
+                Rule::MissingOwner,
+                format!(
+                    "{} has no owner like `{}(alice): ...`",
+                    todo.kind, todo.kind
+                ),
+            );
+        }
+        if required && policy.require_issue && todo.issue.is_none() {
+            violate(
+                todo,
+                Rule::MissingIssue,
+                format!(
+                    "{} refers to no issue like `{}(#123): ...`",
+                    todo.kind, todo.kind
+                ),
+            );
+        }
+        if policy.forbid_overdue
+            && let Some(due) = todo.due
+            && due < today
+        {
+            violate(
+                todo,
+                Rule::Overdue,
+                format!("{} was due on {}", todo.kind, due),
+            );
+        }
+        if policy.forbid.iter().any(|forbid| forbid.matches(todo)) {
+            violate(
+                todo,
+                Rule::ForbiddenMarker,
+                format!("{} is not allowed in this file", todo.kind),
+            );
+        }
+    }
+
+    // the first todo over the limit is reported for the whole file
+    if let Some(max) = policy.max_per_file {
+        let mut paths: Vec<&Path> = todos.iter().map(|todo| todo.path.as_path()).collect();
+        paths.sort();
+        paths.dedup();
+        for path in paths {
+            let mut file_todos = todos.iter().filter(|todo| todo.path == path);
+            let count = file_todos.clone().count();
+            if let Some(todo) = file_todos.nth(max) {
+                violate(
+                    todo,
+                    Rule::TooManyTodos,
+                    format!("file has {} todos but at most {} are allowed", count, max),
+                );
+            }
+        }
+    }
+
+    violations.sort_by_key(|violation| {
+        let todo = violation.todo;
+        (&todo.path, todo.cell, todo.line_number)
+    });
+    violations
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::Config;
+    use crate::todo::{Marker, Options, parse, parse_with};
+
+    #[test]
+    fn test_check() {
+        let mut todos = parse(
+            r#"// TODO(alice, #1, 2025-01-01): overdue
+// XXX(bob, #2): forbidden
+// TODO(carol): no issue
+"#,
+            "/repo/src/main.rs",
+        );
+        let mut options = Options::default();
+        options.markers.push(Marker::new("NOTE", Severity::Info));
+        todos.extend(parse_with(
+            "# XXX(dave, #3): allowed\n# NOTE: no issue needed\n",
+            "/repo/scripts/run.py",
+            &options,
+        ));
+
+        let policy: Policy = toml::from_str(
+            r#"
+require_issue = true
+min_severity = "warning"
+forbid_overdue = true
+max_per_file = 2
+
+[[forbid]]
+markers = ["XXX"]
+globs = ["**/src/**"]
+"#,
+        )
+        .unwrap();
+
+        let today = Date::from_ymd(2025, 6, 1).unwrap();
+        let severity = |kind: &str| match kind {
+            "NOTE" => Severity::Info,
+            _ => Severity::Warning,
+        };
+        let violations = check(&todos, &policy, today, severity)
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            violations,
+            vec![
+                "/repo/src/main.rs:1: overdue: TODO was due on 2025-01-01".to_string(),
+                "/repo/src/main.rs:2: forbidden-marker: XXX is not allowed in this file".into(),
+                "/repo/src/main.rs:3: missing-issue: TODO refers to no issue like `TODO(#123): ...`"
+                    .into(),
+                "/repo/src/main.rs:3: too-many-todos: file has 3 todos but at most 2 are allowed"
+                    .into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_default_config() {
+        let path = std::env::temp_dir().join(format!("owl-test-policy-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        let config = Config::open(&path.join("config.toml"));
+        let _ = std::fs::remove_dir_all(&path);
+
+        let todos = parse("// TODO: x\n", "main.rs");
+        let policy = Policy {
+            require_owner: true,
+            ..Default::default()
+        };
+        let violations = check(&todos, &policy, Date::today(), |kind| config.severity(kind));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule, Rule::MissingOwner);
+    }
+}
@@ -0,0 +1,366 @@
+use crate::frontmatter::FrontMatter;
+use crate::task::heading;
+use std::path::{Component, Path, PathBuf};
+
+/// a task which is created from a todo by `owl todo promote`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draft {
+    pub title: String,
+    pub id: String,
+    pub assignee: Option<String>,
+    pub deadline: Option<String>,
+    /// the file of the todo relative to the markdown file of the task, sources are files so the
+    /// line of the todo is not recorded
+    pub source: PathBuf,
+    /// the continuation lines of the todo
+    pub notes: String,
+}
+
+impl Draft {
+    fn render(&self, level: usize) -> Vec<String> {
+        let mut lines = vec![
+            format!("{} TASK: {}", "#".repeat(level), self.title),
+            format!("> ID: {}", self.id),
+        ];
+        if let Some(assignee) = self.assignee.as_deref() {
+            lines.push(format!("> ASSIGNEE: {}", assignee));
+        }
+        if let Some(deadline) = self.deadline.as_deref() {
+            lines.push(format!("> DEADLINE: {}", deadline));
+        }
+        lines.push(format!("> SOURCES: {}", self.source.to_string_lossy()));
+        lines.extend(self.notes.lines().map(|line| line.to_string()));
+        lines
+    }
+}
+
+/// turns `title` into an id like `fix-the-parser`
+///
+/// # Example
+/// ```
+/// use owl::promote::slug;
+/// assert_eq!(slug("Fix the parser, it's slow!"), "fix-the-parser-it-s-slow");
+/// ```
+pub fn slug(title: &str) -> String {
+    let mut slug = String::new();
+    for word in title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        // long titles are cut at a word
+        if !slug.is_empty() && slug.len() + word.len() >= 40 {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_lowercase());
+    }
+    if slug.is_empty() {
+        slug.push_str("task");
+    }
+    slug
+}
+
+/// returns the path which leads from the directory `from` to `to`, both paths are absolute
+pub fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from = from.components().collect::<Vec<_>>();
+    let to = to.components().collect::<Vec<_>>();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut path = PathBuf::new();
+    for _ in common..from.len() {
+        path.push(Component::ParentDir);
+    }
+    for component in &to[common..] {
+        path.push(component);
+    }
+    path
+}
+
+/// inserts `draft` into the markdown `body` at the end of the section whose heading path is
+/// `prefix`, eg. `Projects/owl`, headings of the prefix which do not exist yet are appended
+///
+/// returns the new body and the line number of the heading of the task
+///
+/// # Errors
+/// fails if the prefix of the front matter of `body` is not the start of `prefix` or the headings
+/// would be nested deeper than 6 levels
+pub fn insert(body: &str, prefix: &str, draft: &Draft) -> Result<(String, usize), String> {
+    let (front_matter, front_matter_len) = FrontMatter::parse(body);
+    let lines = body.lines().collect::<Vec<_>>();
+    let components = prefix
+        .split('/')
+        .map(|component| component.trim())
+        .filter(|component| !component.is_empty())
+        .collect::<Vec<_>>();
+
+    // headings enclosing the current line with their level
+    let mut stack: Vec<(usize, &str)> = Vec::new();
+    if let Some(base) = front_matter.prefix.as_deref() {
+        if components.first() != Some(&base) {
+            return Err(format!(
+                "the front matter sets the prefix {:?} which does not start {:?}",
+                base, prefix
+            ));
+        }
+        stack.push((0, base));
+    }
+
+    // the deepest heading of the prefix found so far as number of matched components, line index
+    // and level, the end of the file is the section of the whole file
+    let mut best = (stack.len(), None, 0);
+    for (i, line) in lines.iter().enumerate().skip(front_matter_len) {
+        let Some((level, text)) = heading(line) else {
+            continue;
+        };
+        let text = text.trim();
+        while stack.last().is_some_and(|(l, _)| *l >= level) {
+            stack.pop();
+        }
+        if ["TASK:", "DONE:", "EVENT:"]
+            .iter()
+            .any(|keyword| text.starts_with(keyword))
+        {
+            continue;
+        }
+        stack.push((level, text));
+
+        let depth = stack.len();
+        let matches = depth <= components.len()
+            && stack
+                .iter()
+                .zip(&components)
+                .all(|((_, text), component)| text == component);
+        if matches && depth > best.0 {
+            best = (depth, Some(i), level);
+        }
+    }
+
+    let (matched, index, level) = best;
+    let mut end = match index {
+        Some(index) => lines[index + 1..]
+            .iter()
+            .position(|line| heading(line).is_some_and(|(l, _)| l <= level))
+            .map(|position| index + 1 + position)
+            .unwrap_or(lines.len()),
+        None => lines.len(),
+    };
+    let start = index.map(|index| index + 1).unwrap_or(front_matter_len);
+    while end > start && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+
+    let missing = &components[matched..];
+    let task_level = level + missing.len() + 1;
+    if task_level > 6 {
+        return Err(format!(
+            "{:?} is nested too deep for a markdown heading",
+            prefix
+        ));
+    }
+
+    let mut inserted = Vec::new();
+    for (i, component) in missing.iter().enumerate() {
+        inserted.push(format!("{} {}", "#".repeat(level + i + 1), component));
+    }
+    inserted.extend(draft.render(task_level));
+
+    let mut result = lines[..end]
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>();
+    if result.last().is_some_and(|line| !line.trim().is_empty()) {
+        result.push(String::new());
+    }
+    let line_number = result.len() + missing.len() + 1;
+    result.extend(inserted);
+    if lines.get(end).is_some_and(|line| !line.trim().is_empty()) {
+        result.push(String::new());
+    }
+    result.extend(lines[end..].iter().map(|line| line.to_string()));
+
+    let mut body = result.join("\n");
+    body.push('\n');
+    Ok((body, line_number))
+}
+
+/// adds the annotation `task:<id>` to the todo with the marker `kind` in `line`
+///
+/// returns `None` if the marker is not found, eg. for `\todo{...}` in LaTeX
+///
+/// # Example
+/// ```
+/// use owl::promote::link;
+/// assert_eq!(
+///     link("    // TODO(alice): fix", "TODO", "fix").as_deref(),
+///     Some("    // TODO(alice, task:fix): fix")
+/// );
+/// assert_eq!(
+///     link("# FIXME: slow", "FIXME", "slow").as_deref(),
+///     Some("# FIXME(task:slow): slow")
+/// );
+/// ```
+pub fn link(line: &str, kind: &str, id: &str) -> Option<String> {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+
+    let (start, _) = line.match_indices(kind).find(|(i, _)| {
+        let before = line[..*i].chars().next_back();
+        let after = line[i + kind.len()..].chars().next();
+        !before.is_some_and(is_identifier) && matches!(after, Some('(' | ':'))
+    })?;
+    let end = start + kind.len();
+
+    if line[end..].starts_with('(') {
+        let close = end + line[end..].find(')')?;
+        let annotation = line[end + 1..close].trim();
+        let separator = if annotation.is_empty() { "" } else { ", " };
+        Some(format!(
+            "{}{}{}task:{}{}",
+            &line[..end + 1],
+            annotation,
+            separator,
+            id,
+            &line[close..]
+        ))
+    } else {
+        Some(format!("{}(task:{}){}", &line[..end], id, &line[end..]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::task::Task;
+
+    fn draft() -> Draft {
+        Draft {
+            title: "Cache lookups".into(),
+            id: "cache-lookups".into(),
+            assignee: Some("alice".into()),
+            deadline: Some("2025-09-01".into()),
+            source: "../src/lib.rs".into(),
+            notes: "they are slow".into(),
+        }
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("  Cache lookups  "), "cache-lookups");
+        assert_eq!(slug("!!!"), "task");
+        assert_eq!(
+            slug("replace the hand written lexer with a generated one because"),
+            "replace-the-hand-written-lexer-with-a"
+        );
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(
+            relative_path("/home/notes".as_ref(), "/home/code/owl/src/lib.rs".as_ref()),
+            PathBuf::from("../code/owl/src/lib.rs")
+        );
+        assert_eq!(
+            relative_path("/home/notes".as_ref(), "/home/notes/a.rs".as_ref()),
+            PathBuf::from("a.rs")
+        );
+    }
+
+    #[test]
+    fn test_insert() {
+        let body = r#"# Projects
+## owl
+### TASK: Release
+
+## fish
+### TASK: Feed
+# Uni
+"#;
+        let (got, line_number) = insert(body, "Projects/owl", &draft()).unwrap();
+        assert_eq!(
+            got,
+            r#"# Projects
+## owl
+### TASK: Release
+
+### TASK: Cache lookups
+> ID: cache-lookups
+> ASSIGNEE: alice
+> DEADLINE: 2025-09-01
+> SOURCES: ../src/lib.rs
+they are slow
+
+## fish
+### TASK: Feed
+# Uni
+"#
+        );
+        assert_eq!(line_number, 5);
+
+        let tasks = Task::parse(&got, "/home/notes/projects.md");
+        let task = tasks.iter().find(|task| task.line_number == line_number);
+        let task = task.unwrap();
+        assert_eq!(task.heading_path(), "Projects/owl/Cache lookups");
+        assert_eq!(task.id.as_deref(), Some("cache-lookups"));
+        assert_eq!(
+            task.sources,
+            vec![PathBuf::from("/home/notes/../src/lib.rs")]
+        );
+        assert_eq!(task.assignees, vec!["alice".to_string()]);
+        assert_eq!(task.body, "they are slow");
+    }
+
+    #[test]
+    fn test_insert_missing_headings() {
+        let draft = Draft {
+            assignee: None,
+            deadline: None,
+            notes: String::new(),
+            ..draft()
+        };
+
+        let body = "# Projects\n## fish\n### TASK: Feed\n# Uni\n";
+        let (got, line_number) = insert(body, "Projects/owl", &draft).unwrap();
+        assert_eq!(
+            got,
+            r#"# Projects
+## fish
+### TASK: Feed
+
+## owl
+### TASK: Cache lookups
+> ID: cache-lookups
+> SOURCES: ../src/lib.rs
+
+# Uni
+"#
+        );
+        assert_eq!(line_number, 6);
+
+        let (got, line_number) = insert("", "", &draft).unwrap();
+        assert_eq!(
+            got,
+            "# TASK: Cache lookups\n> ID: cache-lookups\n> SOURCES: ../src/lib.rs\n"
+        );
+        assert_eq!(line_number, 1);
+
+        let body = "---\nowl:\n  prefix: Projects\n---\n## TASK: Release\n";
+        let (got, line_number) = insert(body, "Projects/owl", &draft).unwrap();
+        assert!(got.ends_with("## TASK: Release\n\n# owl\n## TASK: Cache lookups\n> ID: cache-lookups\n> SOURCES: ../src/lib.rs\n"));
+        assert_eq!(line_number, 8);
+        assert!(insert(body, "Uni", &draft).is_err());
+    }
+
+    #[test]
+    fn test_link() {
+        assert_eq!(
+            link("x = 1 # TODO(): y", "TODO", "y").as_deref(),
+            Some("x = 1 # TODO(task:y): y")
+        );
+        assert_eq!(
+            link("// NOTODO: TODO: x", "TODO", "x").as_deref(),
+            Some("// NOTODO: TODO(task:x): x")
+        );
+        assert_eq!(link("\\todo{x}", "TODO", "x"), None);
+    }
+}
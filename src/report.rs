@@ -0,0 +1,283 @@
+use crate::todo::{Severity, Todo};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// a 64 bit FNV-1a hash, unlike the hashers of the standard library it is stable across versions
+/// which keeps fingerprints stable
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// returns a fingerprint for every todo which stays the same when the todo moves inside its
+/// file, repeated todos with the same content are told apart by their occurrence
+fn fingerprints(todos: &[&Todo]) -> Vec<String> {
+    let mut occurrences = HashMap::new();
+    todos
+        .iter()
+        .map(|todo| {
+            let content = format!(
+                "{}\0{}\0{}\0{}",
+                todo.path.to_string_lossy(),
+                todo.kind,
+                todo.title,
+                todo.body
+            );
+            let occurrence = occurrences.entry(content.clone()).or_insert(0);
+            *occurrence += 1;
+            format!(
+                "{:016x}",
+                fnv1a(format!("{}\0{}", content, occurrence).as_bytes())
+            )
+        })
+        .collect()
+}
+
+/// the path of `todo` relative to `root` if it is inside of it
+fn relative<'a>(todo: &'a Todo, root: &Path) -> std::borrow::Cow<'a, str> {
+    todo.path
+        .strip_prefix(root)
+        .unwrap_or(&todo.path)
+        .to_string_lossy()
+}
+
+/// the uri of the file of `todo` for SARIF, which is relative to `root` if the file is inside of
+/// it and a `file://` uri otherwise
+fn uri(todo: &Todo, root: &Path) -> String {
+    let (scheme, path) = match todo.path.strip_prefix(root) {
+        Ok(relative) => ("", relative),
+        Err(_) => ("file://", todo.path.as_path()),
+    };
+    let mut uri = scheme.to_string();
+    for byte in path.as_os_str().as_encoded_bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(byte) {
+            uri.push(*byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+/// the text of a todo with its marker, cell and body
+fn message(todo: &Todo) -> String {
+    let mut message = format!("{}: {}", todo.kind, todo.title);
+    if let Some(cell) = todo.cell {
+        message.push_str(&format!(" (cell {})", cell));
+    }
+    if !todo.body.is_empty() {
+        message.push('\n');
+        message.push_str(&todo.body);
+    }
+    message
+}
+
+/// returns `todos` as a SARIF 2.1.0 log with a rule per marker, paths are relative to `root`
+///
+/// todos in notebooks have no region because their line numbers count from the start of their
+/// cell, the cell is part of the message instead
+///
+/// # Example
+/// ```
+/// use owl::report::sarif;
+/// use owl::todo::{Severity, parse};
+/// let todos = parse("// FIXME: overflow\n", "/repo/src/main.rs");
+/// let todos = todos.iter().collect::<Vec<_>>();
+///
+/// let log = sarif(&todos, "/repo".as_ref(), |_| Severity::Error);
+/// let result = &log["runs"][0]["results"][0];
+/// assert_eq!(result["ruleId"], "FIXME");
+/// assert_eq!(result["level"], "error");
+/// assert_eq!(
+///     result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+///     "src/main.rs"
+/// );
+/// ```
+pub fn sarif(todos: &[&Todo], root: &Path, severity: impl Fn(&str) -> Severity) -> Value {
+    let level = |kind: &str| match severity(kind) {
+        Severity::Info => "note",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    };
+
+    let mut kinds: Vec<&str> = Vec::new();
+    for todo in todos {
+        if !kinds.contains(&todo.kind.as_str()) {
+            kinds.push(&todo.kind);
+        }
+    }
+    let rules = kinds
+        .iter()
+        .map(|kind| {
+            json!({
+                "id": kind,
+                "shortDescription": { "text": format!("{} comment", kind) },
+                "defaultConfiguration": { "level": level(kind) },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let results = todos
+        .iter()
+        .zip(fingerprints(todos))
+        .map(|(todo, fingerprint)| {
+            let mut location = json!({
+                "artifactLocation": { "uri": uri(todo, root) },
+            });
+            if todo.cell.is_none() {
+                location["region"] = json!({
+                    "startLine": todo.line_number,
+                    "endLine": todo.end_line_number,
+                });
+            }
+            json!({
+                "ruleId": todo.kind,
+                "ruleIndex": kinds.iter().position(|kind| *kind == todo.kind),
+                "level": level(&todo.kind),
+                "message": { "text": message(todo) },
+                "locations": [{ "physicalLocation": location }],
+                "partialFingerprints": { "owlTodo/v1": fingerprint },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "owl",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// returns `todos` as a Code Climate report like GitLab reads it for code quality, paths are
+/// relative to `root`
+///
+/// # Example
+/// ```
+/// use owl::report::codeclimate;
+/// use owl::todo::{Severity, parse};
+/// let todos = parse("// TODO: cache\n", "/repo/src/main.rs");
+/// let todos = todos.iter().collect::<Vec<_>>();
+///
+/// let report = codeclimate(&todos, "/repo".as_ref(), |_| Severity::Info);
+/// assert_eq!(report[0]["check_name"], "TODO");
+/// assert_eq!(report[0]["severity"], "info");
+/// assert_eq!(report[0]["location"]["lines"]["begin"], 1);
+/// ```
+pub fn codeclimate(todos: &[&Todo], root: &Path, severity: impl Fn(&str) -> Severity) -> Value {
+    let issues = todos
+        .iter()
+        .zip(fingerprints(todos))
+        .map(|(todo, fingerprint)| {
+            let severity = match severity(&todo.kind) {
+                Severity::Info => "info",
+                Severity::Warning => "minor",
+                Severity::Error => "major",
+            };
+            json!({
+                "type": "issue",
+                "check_name": todo.kind,
+                "description": format!("{}: {}", todo.kind, todo.title),
+                "content": { "body": message(todo) },
+                "categories": ["Clarity"],
+                "severity": severity,
+                "fingerprint": fingerprint,
+                "location": {
+                    "path": relative(todo, root),
+                    "lines": {
+                        "begin": todo.line_number,
+                        "end": todo.end_line_number,
+                    },
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+    Value::Array(issues)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::todo::parse;
+
+    #[test]
+    fn test_fingerprints() {
+        let old = parse("// TODO: same\n// TODO: same\n// HACK: other\n", "/a.rs");
+        let new = parse("\n// TODO: same\n// HACK: other\n// TODO: same\n", "/a.rs");
+
+        let old = fingerprints(&old.iter().collect::<Vec<_>>());
+        let new = fingerprints(&new.iter().collect::<Vec<_>>());
+        assert_ne!(old[0], old[1]);
+        assert_eq!(old[0], new[0]);
+        assert_eq!(old[1], new[2]);
+        assert_eq!(old[2], new[1]);
+    }
+
+    #[test]
+    fn test_sarif() {
+        let todos = parse(
+            "/* TODO: a\n *       b */\n// XXX: c\n// TODO: d\n",
+            "/repo/lib.rs",
+        );
+        let todos = todos.iter().collect::<Vec<_>>();
+        let severity = |kind: &str| match kind {
+            "XXX" => Severity::Warning,
+            _ => Severity::Info,
+        };
+
+        let log = sarif(&todos, Path::new("/other"), severity);
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "TODO");
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "XXX");
+
+        let result = &run["results"][0];
+        assert_eq!(result["level"], "note");
+        assert_eq!(result["message"]["text"], "TODO: a\nb");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "file:///repo/lib.rs");
+        assert_eq!(location["region"]["startLine"], 1);
+        assert_eq!(location["region"]["endLine"], 2);
+
+        assert_eq!(run["results"][1]["ruleIndex"], 1);
+        assert_eq!(run["results"][1]["level"], "warning");
+        assert_eq!(run["results"][2]["ruleIndex"], 0);
+    }
+
+    #[test]
+    fn test_sarif_locations() {
+        let mut todos = parse("// TODO: spaced\n", "/repo/my notes/a#b.rs");
+        todos.extend(parse(
+            r##"{"cells": [{"cell_type": "markdown", "source": ["Text\n", "# TODO: cell"]}]}"##,
+            "/repo/analysis.ipynb",
+        ));
+        let todos = todos.iter().collect::<Vec<_>>();
+
+        let log = sarif(&todos, Path::new("/repo"), |_| Severity::Info);
+        let results = &log["runs"][0]["results"];
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "my%20notes/a%23b.rs");
+        assert_eq!(location["region"]["startLine"], 1);
+
+        let location = &results[1]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "analysis.ipynb");
+        assert!(location.get("region").is_none());
+        assert_eq!(results[1]["message"]["text"], "TODO: cell (cell 1)");
+
+        let log = sarif(&todos, Path::new("/other"), |_| Severity::Info);
+        let location = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+        assert_eq!(
+            location["artifactLocation"]["uri"],
+            "file:///repo/my%20notes/a%23b.rs"
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use crate::{
+    store::Store,
+    task::Task,
+    time::{Duration, Span},
+};
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+
+/// which date of the referenced task a `Relative` date is anchored to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Anchor {
+    /// the CLOSED timestamp, the deadline or the scheduled date of the referenced task
+    After,
+    /// the scheduled date or the deadline of the referenced task
+    Before,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Offset {
+    Add(Duration),
+    Sub(Duration),
+}
+
+/// a date which is given relative to another task like `after(write-draft) +2d`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Relative {
+    pub anchor: Anchor,
+    pub reference: String,
+    pub offset: Option<Offset>,
+}
+
+impl FromStr for Relative {
+    type Err = ();
+
+    /// parses `after(<task>) [+-]<duration>` and `before(<task>) [+-]<duration>`
+    ///
+    /// # Example
+    /// ```
+    /// use owl::schedule::{Anchor, Offset, Relative};
+    /// use owl::time::Duration;
+    ///
+    /// let relative: Relative = "after(write-draft) +2d".parse().unwrap();
+    /// assert_eq!(
+    ///     relative,
+    ///     Relative {
+    ///         anchor: Anchor::After,
+    ///         reference: "write-draft".into(),
+    ///         offset: Some(Offset::Add(Duration::Day(2))),
+    ///     }
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (anchor, rest) = if let Some(rest) = s.strip_prefix("after(") {
+            (Anchor::After, rest)
+        } else if let Some(rest) = s.strip_prefix("before(") {
+            (Anchor::Before, rest)
+        } else {
+            return Err(());
+        };
+
+        let end = rest.rfind(')').ok_or(())?;
+        let reference = rest[..end].trim();
+        let offset = rest[end + 1..].trim();
+
+        if reference.is_empty() {
+            return Err(());
+        }
+
+        let offset = if let Some(duration) = offset.strip_prefix('+') {
+            Some(Offset::Add(duration.parse()?))
+        } else if let Some(duration) = offset.strip_prefix('-') {
+            Some(Offset::Sub(duration.parse()?))
+        } else if offset.is_empty() {
+            None
+        } else {
+            return Err(());
+        };
+
+        Ok(Self {
+            anchor,
+            reference: reference.into(),
+            offset,
+        })
+    }
+}
+
+/// a problem found while resolving relative dates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    pub line_number: usize,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}",
+            self.path.to_str().unwrap(),
+            self.line_number,
+            self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    Unvisited,
+    InProgress,
+    Done,
+}
+
+struct Resolver<'a> {
+    tasks: &'a mut [Task],
+    visits: Vec<Visit>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Resolver<'_> {
+    fn diagnose(&mut self, i: usize, message: String) {
+        let task = &self.tasks[i];
+        let diagnostic = Diagnostic {
+            path: task.path.clone(),
+            line_number: task.line_number,
+            message,
+        };
+        log::warn!("{}", diagnostic);
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn visit(&mut self, i: usize) {
+        if self.visits[i] != Visit::Unvisited {
+            return;
+        }
+        self.visits[i] = Visit::InProgress;
+
+        if let Some(relative) = self.tasks[i].scheduled_relative.clone() {
+            self.tasks[i].scheduled = self.evaluate(i, &relative);
+        }
+        if let Some(relative) = self.tasks[i].deadline_relative.clone() {
+            self.tasks[i].deadline = self.evaluate(i, &relative);
+        }
+
+        self.visits[i] = Visit::Done;
+    }
+
+    /// a reference resolves to every task it matches like in `deps::Graph`, `after` is anchored
+    /// to the latest date of them and `before` to the earliest
+    fn evaluate(&mut self, i: usize, relative: &Relative) -> Option<Span> {
+        let matches = (0..self.tasks.len())
+            .filter(|&j| self.tasks[j].matches(&relative.reference))
+            .collect::<Vec<_>>();
+        if matches.is_empty() {
+            self.diagnose(i, format!("unresolved reference {:?}", relative.reference));
+            return None;
+        }
+
+        if matches.iter().any(|&j| self.visits[j] == Visit::InProgress) {
+            self.diagnose(
+                i,
+                format!("cycle in relative dates through {:?}", relative.reference),
+            );
+            return None;
+        }
+        for &j in matches.iter() {
+            self.visit(j);
+        }
+
+        let dates = matches.iter().filter_map(|&j| {
+            let other = &self.tasks[j];
+            match relative.anchor {
+                Anchor::After => other.closed.or(other.deadline).or(other.scheduled),
+                Anchor::Before => other.scheduled.or(other.deadline),
+            }
+        });
+        let base = match relative.anchor {
+            Anchor::After => dates.max_by_key(|span| span.date),
+            Anchor::Before => dates.min_by_key(|span| span.date),
+        };
+        let Some(base) = base else {
+            self.diagnose(
+                i,
+                format!("referenced task {:?} has no date", relative.reference),
+            );
+            return None;
+        };
+
+        let date = match relative.offset {
+            Some(Offset::Add(duration)) => base.date.add_duration(duration),
+            Some(Offset::Sub(duration)) => base.date.sub_duration(duration),
+            None => Some(base.date),
+        };
+
+        match date {
+            Some(date) => Some(Span::new(date, None, None)),
+            None => {
+                self.diagnose(i, "relative date is out of range".into());
+                None
+            }
+        }
+    }
+}
+
+/// evaluates the relative scheduled dates and deadlines of every task in `store` and stores
+/// problems like cycles or unresolved references in `store.diagnostics`
+pub fn resolve(store: &mut Store) {
+    let mut resolver = Resolver {
+        visits: vec![Visit::Unvisited; store.tasks.len()],
+        tasks: &mut store.tasks,
+        diagnostics: Vec::new(),
+    };
+
+    for i in 0..resolver.tasks.len() {
+        resolver.visit(i);
+    }
+
+    store.diagnostics = resolver.diagnostics;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::time::Date;
+
+    fn store(body: &str) -> Store {
+        Store {
+            tasks: Task::parse(body, "/home/user/plan.md"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_relative() {
+        assert_eq!(
+            "before(Uni/Exam) -1w".parse::<Relative>(),
+            Ok(Relative {
+                anchor: Anchor::Before,
+                reference: "Uni/Exam".into(),
+                offset: Some(Offset::Sub(Duration::Week(1))),
+            })
+        );
+        assert_eq!(
+            "after(draft)".parse::<Relative>(),
+            Ok(Relative {
+                anchor: Anchor::After,
+                reference: "draft".into(),
+                offset: None,
+            })
+        );
+        assert!("after() +1d".parse::<Relative>().is_err());
+        assert!("after(draft) 1d".parse::<Relative>().is_err());
+        assert!("after(draft) +".parse::<Relative>().is_err());
+        assert!("after(draft) +2ä".parse::<Relative>().is_err());
+        assert!("later(draft)".parse::<Relative>().is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut store = store(
+            "
+# TASK: Review draft
+> SCHEDULED: after(draft) +2d
+# DONE: Write draft
+> ID: draft
+> SCHEDULED: 2025-07-01
+> CLOSED: 2025-07-03 12:00
+# TASK: Submit
+> DEADLINE: 2025-07-20
+# TASK: Proofread
+> DEADLINE: before(Submit) -1w
+> SCHEDULED: after(Review draft)
+",
+        );
+        resolve(&mut store);
+
+        let date = |s: &str| Some(Span::new(s.parse::<Date>().unwrap(), None, None));
+        assert_eq!(store.tasks[0].scheduled, date("2025-07-05"));
+        assert_eq!(store.tasks[3].deadline, date("2025-07-13"));
+        assert_eq!(store.tasks[3].scheduled, date("2025-07-05"));
+        assert!(store.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_ambiguous() {
+        let mut store = store(
+            "
+# TASK: Exam
+> ID: exam
+> DEADLINE: 2025-07-10
+# TASK: Retake
+> ID: exam
+> DEADLINE: 2025-07-20
+# TASK: Oral exam
+> ID: exam
+# TASK: Celebrate
+> SCHEDULED: after(exam) +1d
+> BLOCKED_BY: exam
+# TASK: Study
+> DEADLINE: before(exam) -1d
+",
+        );
+        resolve(&mut store);
+
+        let date = |s: &str| Some(Span::new(s.parse::<Date>().unwrap(), None, None));
+        assert_eq!(store.tasks[3].scheduled, date("2025-07-21"));
+        assert_eq!(store.tasks[4].deadline, date("2025-07-09"));
+        assert!(store.diagnostics.is_empty());
+
+        // the dependency graph resolves the same reference to the same tasks
+        let graph = crate::deps::Graph::new(&store.tasks);
+        assert_eq!(graph.blockers[3], vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_resolve_diagnostics() {
+        let mut store = store(
+            "
+# TASK: A
+> SCHEDULED: after(B)
+# TASK: B
+> SCHEDULED: after(A) +1d
+# TASK: C
+> DEADLINE: before(unknown)
+# TASK: D
+> DEADLINE: before(E)
+# TASK: E
+",
+        );
+        resolve(&mut store);
+
+        let messages = store
+            .diagnostics
+            .iter()
+            .map(|d| (d.line_number, d.message.as_str()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            vec![
+                (4, "cycle in relative dates through \"A\""),
+                (2, "referenced task \"B\" has no date"),
+                (6, "unresolved reference \"unknown\""),
+                (8, "referenced task \"E\" has no date"),
+            ]
+        );
+        assert!(store.tasks.iter().all(|t| t.scheduled.is_none()));
+    }
+}
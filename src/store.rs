@@ -1,4 +1,6 @@
+use crate::event::Event;
 use crate::file::File;
+use crate::schedule::Diagnostic;
 use crate::task::Task;
 use crate::todo::Todo;
 use std::path::Path;
@@ -8,13 +10,25 @@ pub struct Store {
     pub files: Vec<File>,
     pub todos: Vec<Todo>,
     pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub events: Vec<Event>,
+    /// the fingerprint of the config the files were parsed with, see `Config::fingerprint`
+    #[serde(default)]
+    pub fingerprint: String,
+    /// problems found while resolving references between tasks, see `schedule::resolve`
+    #[serde(skip)]
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl Store {
     /// reads and parses a json encoded store at `path`
     ///
+    /// the store only caches what has been parsed from the scanned files, therefore a store which
+    /// cannot be deserialized, for example because it was written by an older version of owl, is
+    /// discarded and rebuilt by the next scan
+    ///
     /// # Panics
-    /// if reading, or parsing fails the function panics with a meaningful error message
+    /// if reading fails the function panics with a meaningful error message
     pub fn open(path: &Path) -> Self {
         let store = match std::fs::read_to_string(path) {
             Ok(store) => store,
@@ -27,7 +41,10 @@ impl Store {
 
         let store: Store = match serde_json::from_str(&store) {
             Ok(store) => store,
-            Err(err) => panic!("could not read store at: {:?}: {:?}", path, err),
+            Err(err) => {
+                log::warn!("discarding store at: {:?} because: {:?}", path, err);
+                return Self::default();
+            }
         };
 
         log::info!("deserialized store from string");
@@ -58,3 +75,33 @@ impl Store {
         log::info!("wrote store to {:?}", path);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_open() {
+        let path = std::env::temp_dir().join(format!("owl-test-store-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(Store::open(&path), Store::default());
+
+        let store = Store {
+            todos: crate::todo::parse("// TODO: cached\n", "/home/main.rs"),
+            fingerprint: "0123456789abcdef".into(),
+            ..Default::default()
+        };
+        store.close(&path);
+        assert_eq!(Store::open(&path), store);
+
+        // a store of an older version is discarded instead of failing
+        std::fs::write(
+            &path,
+            r#"{"files": [], "todos": [{"title": 1}], "tasks": []}"#,
+        )
+        .unwrap();
+        assert_eq!(Store::open(&path), Store::default());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
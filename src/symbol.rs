@@ -0,0 +1,319 @@
+/// how the scopes of functions and types are delimited in a format, used to find the symbol
+/// which encloses a todo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scopes {
+    /// symbols are not tracked
+    #[default]
+    None,
+    /// scopes are delimited by `{` and `}` like in C or Rust
+    Braces,
+    /// scopes are delimited by indentation like in Python
+    Indentation,
+}
+
+/// keywords which are followed by the name of the declared symbol
+const KEYWORDS: [&str; 16] = [
+    "fn",
+    "func",
+    "function",
+    "fun",
+    "class",
+    "struct",
+    "enum",
+    "union",
+    "trait",
+    "interface",
+    "impl",
+    "mod",
+    "namespace",
+    "object",
+    "protocol",
+    "extension",
+];
+
+/// keywords which look like a function call followed by a block
+const CONTROL: [&str; 13] = [
+    "if", "else", "for", "while", "switch", "catch", "return", "match", "do", "try", "case", "new",
+    "throw",
+];
+
+fn is_identifier(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// returns the identifier at the start of `text`, `::` separated paths are kept as one identifier
+fn identifier(text: &str) -> Option<&str> {
+    let text = text.trim_start();
+    let mut end = 0;
+    while let Some(c) = text[end..].chars().next() {
+        if is_identifier(c) {
+            end += c.len_utf8();
+        } else if text[end..].starts_with("::") {
+            end += 2;
+        } else {
+            break;
+        }
+    }
+    let end = text[..end].trim_end_matches(':').len();
+    (end > 0).then(|| &text[..end])
+}
+
+/// skips the group like `<T>` or `(r *T)` at the start of `text`
+fn skip_group(text: &str, open: char, close: char) -> &str {
+    let text = text.trim_start();
+    if !text.starts_with(open) {
+        return text;
+    }
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return &text[i + 1..];
+            }
+        }
+    }
+    ""
+}
+
+/// returns the name declared by a line of a language with braces, eg. `parse` for
+/// `pub fn parse(body: &str) -> Vec<Task> {` or `main` for `int main(void)`
+fn declaration(line: &str) -> Option<String> {
+    let line = line.trim().trim_start_matches(['}', ' ']);
+
+    // declarations introduced by a keyword
+    let mut rest = line;
+    while let Some(start) = rest.find(|c: char| is_identifier(c)) {
+        let word_start = &rest[start..];
+        let end = word_start
+            .find(|c: char| !is_identifier(c))
+            .unwrap_or(word_start.len());
+        let (word, after) = word_start.split_at(end);
+        // keywords are whole words and not preceded by a path separator or a quote
+        let preceded = rest[..start].ends_with(['.', ':', '"', '\'']);
+        if !preceded && KEYWORDS.contains(&word) {
+            let name = match word {
+                "impl" => {
+                    let after = skip_group(after, '<', '>');
+                    let target = match after.find(" for ") {
+                        Some(i) => &after[i + 5..],
+                        None => after,
+                    };
+                    identifier(target)
+                }
+                "func" => identifier(skip_group(after, '(', ')')),
+                _ => identifier(after),
+            };
+            return name.map(|name| name.to_string());
+        }
+        if CONTROL.contains(&word) {
+            return None;
+        }
+        rest = after;
+    }
+
+    // functions like `int main(void)` whose name is preceded by at least a return type
+    let open = line.find('(')?;
+    let head = &line[..open];
+    if head.contains(['=', ';', '"', '.', '>', '-']) && !head.contains("::") {
+        return None;
+    }
+    let words = head.split_whitespace().collect::<Vec<_>>();
+    if words.len() < 2 || CONTROL.contains(&words[0]) {
+        return None;
+    }
+    let name = words.last()?.trim_start_matches(['*', '&']);
+    identifier(name)
+        .filter(|n| n.len() == name.len())
+        .map(|name| name.to_string())
+}
+
+fn symbols_braces(body: &str) -> Vec<Option<String>> {
+    // open scopes with the name of the symbol they belong to
+    let mut stack: Vec<Option<String>> = Vec::new();
+    let mut pending: Option<String> = None;
+    let mut symbols = Vec::new();
+
+    let path = |stack: &[Option<String>]| {
+        let names = stack.iter().flatten().cloned().collect::<Vec<_>>();
+        (!names.is_empty()).then(|| names.join("::"))
+    };
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.starts_with('*') {
+            symbols.push(path(&stack));
+            continue;
+        }
+
+        if let Some(name) = declaration(line) {
+            pending = Some(name);
+        }
+
+        let before = path(&stack);
+        let mut quote = None;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (quote, c) {
+                (Some(_), '\\') => {
+                    chars.next();
+                }
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => (),
+                (None, '"') => quote = Some('"'),
+                // character literals like '{'
+                (None, '\'') => {
+                    let mut next = chars.clone();
+                    if let (Some(_), Some('\'')) = (next.next(), next.next()) {
+                        chars.next();
+                        chars.next();
+                    }
+                }
+                (None, '/') if chars.peek() == Some(&'/') => break,
+                (None, '{') => stack.push(pending.take()),
+                (None, '}') => {
+                    stack.pop();
+                }
+                (None, ';') => pending = None,
+                _ => (),
+            }
+        }
+        let after = path(&stack);
+
+        // the line which opens or closes a scope belongs to it
+        let depth = |p: &Option<String>| p.as_ref().map(|p| p.len()).unwrap_or(0);
+        symbols.push(if depth(&after) >= depth(&before) {
+            after
+        } else {
+            before
+        });
+    }
+    symbols
+}
+
+fn symbols_indentation(body: &str) -> Vec<Option<String>> {
+    let mut stack: Vec<(usize, String)> = Vec::new();
+    let mut symbols = Vec::new();
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if !trimmed.is_empty() {
+            let indent = line.len() - trimmed.len();
+            while stack.last().is_some_and(|(i, _)| *i >= indent) {
+                stack.pop();
+            }
+
+            let declaration = trimmed
+                .strip_prefix("async ")
+                .unwrap_or(trimmed)
+                .strip_prefix("def ")
+                .or_else(|| trimmed.strip_prefix("class "));
+            if let Some(name) = declaration.and_then(identifier) {
+                stack.push((indent, name.to_string()));
+            }
+        }
+
+        let names = stack.iter().map(|(_, n)| n.as_str()).collect::<Vec<_>>();
+        symbols.push((!names.is_empty()).then(|| names.join(".")));
+    }
+    symbols
+}
+
+/// returns the symbol enclosing each line of `body`, the symbol of line `n` is at index `n - 1`
+///
+/// # Example
+/// ```
+/// use owl::symbol::{Scopes, symbols};
+/// let body = "impl Task {\n    fn parse() {\n        // TODO: faster\n    }\n}";
+///
+/// let symbols = symbols(body, Scopes::Braces);
+/// assert_eq!(symbols[2].as_deref(), Some("Task::parse"));
+/// ```
+pub fn symbols(body: &str, scopes: Scopes) -> Vec<Option<String>> {
+    match scopes {
+        Scopes::None => Vec::new(),
+        Scopes::Braces => symbols_braces(body),
+        Scopes::Indentation => symbols_indentation(body),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_declaration() {
+        let cases = [
+            (
+                "pub(crate) fn parse(body: &str) -> Vec<Task> {",
+                Some("parse"),
+            ),
+            ("impl<'a> Display for Graph<'a> {", Some("Graph")),
+            ("impl Task {", Some("Task")),
+            (
+                "func (s *Server) Handle(w http.ResponseWriter) {",
+                Some("Handle"),
+            ),
+            ("export default class App extends Base {", Some("App")),
+            ("public static void main(String[] args) {", Some("main")),
+            ("int Foo::bar(int x)", Some("Foo::bar")),
+            ("} else if (x) {", None),
+            ("foo(bar, |x| {", None),
+            ("let x = compute(y);", None),
+            ("return helper(x);", None),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(declaration(line).as_deref(), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_symbols_braces() {
+        let body = r#"// TODO: top level
+struct Point {
+    x: i32, // TODO: in struct
+}
+
+int main(void)
+{
+    if (x == '{') {
+        puts("}"); // TODO: in main
+    }
+}
+// TODO: after main
+"#;
+        let symbols = symbols(body, Scopes::Braces);
+        assert_eq!(symbols[0], None);
+        assert_eq!(symbols[2].as_deref(), Some("Point"));
+        assert_eq!(symbols[4], None);
+        assert_eq!(symbols[5], None);
+        assert_eq!(symbols[6].as_deref(), Some("main"));
+        assert_eq!(symbols[8].as_deref(), Some("main"));
+        assert_eq!(symbols[10].as_deref(), Some("main"));
+        assert_eq!(symbols[11], None);
+    }
+
+    #[test]
+    fn test_symbols_indentation() {
+        let body = r#"class Model:
+    # TODO: in class
+    def fit(self):
+
+        # TODO: in method
+        pass
+
+# TODO: top level
+async def main():
+    pass
+"#;
+        let symbols = symbols(body, Scopes::Indentation);
+        assert_eq!(symbols[1].as_deref(), Some("Model"));
+        assert_eq!(symbols[3].as_deref(), Some("Model.fit"));
+        assert_eq!(symbols[4].as_deref(), Some("Model.fit"));
+        assert_eq!(symbols[7], None);
+        assert_eq!(symbols[9].as_deref(), Some("main"));
+    }
+}
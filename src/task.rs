@@ -1,4 +1,10 @@
-use crate::{format::Format, time::Span};
+use crate::{
+    format::{Formats, Parser},
+    frontmatter::FrontMatter,
+    notebook::{CellType, Notebook},
+    schedule::Relative,
+    time::{Date, Span},
+};
 use std::{
     fmt::Display,
     path::{Path, PathBuf},
@@ -30,11 +36,102 @@ pub struct Task {
     pub path: PathBuf,
     pub prefix: String,
     pub title: String,
-    pub sources: Option<PathBuf>,
+    #[serde(deserialize_with = "deserialize_sources")]
+    pub sources: Vec<PathBuf>,
     pub deadline: Option<Span>,
     pub scheduled: Option<Span>,
     pub line_number: usize,
     pub subtasks: Vec<SubTask>,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub blocked_by: Vec<String>,
+    #[serde(default)]
+    pub defer: Option<Date>,
+    #[serde(default)]
+    pub assignees: Vec<String>,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub closed: Option<Span>,
+    /// `deadline` is evaluated from this by `schedule::resolve` if it is set
+    #[serde(default)]
+    pub deadline_relative: Option<Relative>,
+    /// `scheduled` is evaluated from this by `schedule::resolve` if it is set
+    #[serde(default)]
+    pub scheduled_relative: Option<Relative>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// index of the notebook cell starting at `1`, line numbers count from the start of the cell
+    #[serde(default)]
+    pub cell: Option<usize>,
+    /// the directory which `config.directory_prefix` prepended to `prefix`, references to the
+    /// task may leave it out
+    #[serde(skip)]
+    pub directory: Option<String>,
+}
+
+/// stores written before `Task::sources` became a list contain `null` or a single path
+fn deserialize_sources<'de, D>(deserializer: D) -> Result<Vec<PathBuf>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Sources {
+        Many(Vec<PathBuf>),
+        One(Option<PathBuf>),
+    }
+
+    match serde::Deserialize::deserialize(deserializer)? {
+        Sources::Many(sources) => Ok(sources),
+        Sources::One(source) => Ok(source.into_iter().collect()),
+    }
+}
+
+/// collects the targets of every markdown link in `body` which point to local files
+fn links(body: &str) -> Vec<&str> {
+    let mut links = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("](") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find(')') else {
+            break;
+        };
+        let target = rest[..end].split('#').next().unwrap_or("").trim();
+        rest = &rest[end..];
+
+        let is_remote = target.contains("://") || target.starts_with("mailto:");
+        if !target.is_empty() && !is_remote {
+            links.push(target);
+        }
+    }
+    links
+}
+
+/// keywords of the blockquote lines below a task heading which are parsed into fields of `Task`
+const PROPERTIES: [&str; 10] = [
+    "DEADLINE:",
+    "SCHEDULED:",
+    "WORK:",
+    "DEFER:",
+    "ID:",
+    "BLOCKED_BY:",
+    "ASSIGNEE:",
+    "SOURCES:",
+    "CLOSED:",
+    "TAGS:",
+];
+
+/// checks whether `line` is parsed into a field of `Task` and therefore not part of its body
+fn is_property(line: &str) -> bool {
+    if line.starts_with("- [X]") || line.starts_with("- [ ]") {
+        return true;
+    }
+    match line.strip_prefix(">") {
+        Some(line) => PROPERTIES.iter().any(|p| line.trim().starts_with(p)),
+        None => false,
+    }
 }
 
 impl Display for State {
@@ -47,19 +144,49 @@ impl Display for State {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-pub enum SubTask {
-    Done(String),
-    NotDone(String),
+pub struct SubTask {
+    pub title: String,
+    pub done: bool,
+    /// optional date given as `@<span>` at the end of the checkbox item
+    pub date: Option<Span>,
 }
 
 impl SubTask {
-    pub fn is_done(&self) -> bool {
-        match self {
-            SubTask::Done(_) => true,
-            SubTask::NotDone(_) => false,
+    /// parses the text of a checkbox item following the `- [ ]` or `- [X]`
+    ///
+    /// # Example
+    /// ```
+    /// use owl::task::SubTask;
+    /// let subtask = SubTask::new("draft intro @2025-07-03", false);
+    ///
+    /// assert_eq!(subtask.title, "draft intro");
+    /// assert_eq!(subtask.date, "2025-07-03".parse().ok());
+    /// ```
+    pub fn new(text: &str, done: bool) -> Self {
+        let text = text.trim();
+        let dated = text
+            .rfind('@')
+            .filter(|&i| i == 0 || text[..i].ends_with(' '))
+            .and_then(|i| Some((&text[..i], text[i + 1..].parse::<Span>().ok()?)));
+
+        match dated {
+            Some((title, date)) => Self {
+                title: title.trim().into(),
+                done,
+                date: Some(date),
+            },
+            None => Self {
+                title: text.into(),
+                done,
+                date: None,
+            },
         }
     }
 
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
     pub fn is_not_done(&self) -> bool {
         !self.is_done()
     }
@@ -67,26 +194,40 @@ impl SubTask {
 
 impl Display for SubTask {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            SubTask::Done(title) => write!(f, "- [X] {}", title),
-            SubTask::NotDone(title) => write!(f, "- [ ] {}", title),
+        if self.done {
+            write!(f, "- [X] {}", self.title)?;
+        } else {
+            write!(f, "- [ ] {}", self.title)?;
+        }
+        if let Some(date) = self.date {
+            write!(f, " @{}", date)?;
         }
+        Ok(())
     }
 }
 
+/// splits a markdown heading into its level and its text
+pub(crate) fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    line[level..].strip_prefix(' ').map(|text| (level, text))
+}
+
 #[derive(Debug, Clone)]
-struct PrefixBuffer<'a> {
+pub(crate) struct PrefixBuffer<'a> {
     buffer: Vec<(usize, &'a str)>,
 }
 
 impl<'a> PrefixBuffer<'a> {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             buffer: Vec::with_capacity(6),
         }
     }
 
-    fn pop_to(&mut self, level: usize) {
+    pub(crate) fn pop_to(&mut self, level: usize) {
         match self.buffer.last() {
             Some((last_level, _)) if *last_level >= level => {
                 self.buffer.pop();
@@ -96,11 +237,11 @@ impl<'a> PrefixBuffer<'a> {
         }
     }
 
-    fn push(&mut self, level: usize, prefix: &'a str) {
+    pub(crate) fn push(&mut self, level: usize, prefix: &'a str) {
         self.buffer.push((level, prefix));
     }
 
-    fn read(&self) -> String {
+    pub(crate) fn read(&self) -> String {
         let mut s = String::new();
         for (_, part) in self.buffer.iter() {
             s.push_str(part);
@@ -111,22 +252,120 @@ impl<'a> PrefixBuffer<'a> {
     }
 }
 
+/// collects every `@name` mention in `title`
+fn mentions(title: &str) -> Vec<String> {
+    title
+        .split_whitespace()
+        .filter_map(|word| word.strip_prefix('@'))
+        .map(|name| name.trim_end_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|name| !name.is_empty())
+        .map(|name| name.into())
+        .collect()
+}
+
 impl Task {
     fn new(state: State, title: String, prefix: String, path: PathBuf, line_number: usize) -> Self {
         Self {
+            assignees: mentions(&title),
             prefix,
             state,
             title,
             path,
-            sources: None,
+            sources: vec![],
             deadline: None,
             scheduled: None,
             subtasks: vec![],
             work: vec![],
             line_number,
+            id: None,
+            blocked_by: vec![],
+            defer: None,
+            body: String::new(),
+            closed: None,
+            deadline_relative: None,
+            scheduled_relative: None,
+            tags: vec![],
+            cell: None,
+            directory: None,
+        }
+    }
+
+    /// returns `path:line` or `path#cell:line` for tasks in notebooks
+    pub fn location(&self) -> String {
+        let path = self.path.to_str().unwrap();
+        match self.cell {
+            Some(cell) => format!("{}#{}:{}", path, cell, self.line_number),
+            None => format!("{}:{}", path, self.line_number),
+        }
+    }
+
+    /// adds `source` to the sources of the task, relative paths are resolved relative to the
+    /// directory containing the task
+    fn add_source(&mut self, source: &str) {
+        let source = source.trim_start_matches("./");
+        let source = match self.path.parent() {
+            Some(parent) => parent.join(source),
+            None => source.into(),
+        };
+        if !self.sources.contains(&source) {
+            self.sources.push(source);
+        }
+    }
+
+    /// shortens the body of the task to at most `limit` bytes
+    pub fn truncate_body(&mut self, limit: usize) {
+        if self.body.len() <= limit {
+            return;
+        }
+        let mut end = limit;
+        while !self.body.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.body.truncate(end);
+    }
+
+    /// checks whether `name` is one of the assignees of the task
+    pub fn is_assigned_to(&self, name: &str) -> bool {
+        let name = name.trim().trim_start_matches('@');
+        self.assignees.iter().any(|assignee| assignee == name)
+    }
+
+    /// checks whether the task is hidden on `today` because it is deferred to a later date
+    pub fn is_deferred(&self, today: Date) -> bool {
+        self.defer.is_some_and(|defer| today < defer)
+    }
+
+    /// returns the prefix and the title of the task joined by a `/`
+    ///
+    /// # Example
+    /// ```
+    /// use owl::task::Task;
+    /// let task = &Task::parse("# Uni\n## TASK: Exam", "/home/user/uni.md")[0];
+    /// assert_eq!(task.heading_path(), "Uni/Exam");
+    /// ```
+    pub fn heading_path(&self) -> String {
+        if self.prefix.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{}/{}", self.prefix, self.title)
         }
     }
 
+    /// checks whether `reference` refers to this task, either by its id or by its heading path
+    /// with or without the directory prepended by `config.directory_prefix`
+    pub fn matches(&self, reference: &str) -> bool {
+        let reference = reference.trim();
+        let heading_path = self.heading_path();
+        let without_directory = self.directory.as_deref().and_then(|directory| {
+            heading_path
+                .strip_prefix(directory)
+                .and_then(|path| path.strip_prefix('/'))
+        });
+        self.id.as_deref() == Some(reference)
+            || heading_path == reference
+            || without_directory == Some(reference)
+    }
+
     fn handle_heading<'a>(
         line: &'a str,
         path: &Path,
@@ -156,7 +395,7 @@ impl Task {
                 path.into(),
                 line_number,
             ));
-        } else {
+        } else if !line.starts_with("EVENT:") {
             prefix.push(heading_level, line);
         }
     }
@@ -182,7 +421,18 @@ impl Task {
     ///     line_number: 4,
     ///     deadline: None,
     ///     scheduled: None,
-    ///     sources: None,
+    ///     sources: vec![],
+    ///     id: None,
+    ///     blocked_by: vec![],
+    ///     defer: None,
+    ///     assignees: vec![],
+    ///     body: String::new(),
+    ///     closed: None,
+    ///     deadline_relative: None,
+    ///     scheduled_relative: None,
+    ///     tags: vec![],
+    ///     cell: None,
+    ///     directory: None,
     /// };
     ///
     /// let got = Task::parse(body, "/home/user/journal/uni.md");
@@ -192,50 +442,140 @@ impl Task {
     /// # Errors
     /// erroniously formatted tasks will be ignored while parsing
     pub fn parse<P: AsRef<Path>>(body: &str, path: P) -> Vec<Task> {
+        Self::parse_with(body, path, &Formats::default())
+    }
+
+    /// like `parse` but detects the format of the file with `formats`, eg. the formats of the
+    /// config
+    pub fn parse_with<P: AsRef<Path>>(body: &str, path: P, formats: &Formats) -> Vec<Task> {
         let path: &Path = path.as_ref();
-        if Format::new(path) != Format::Markdown {
-            return Vec::new();
+        match formats.detect(path, body).map(|format| format.parser) {
+            Some(Parser::Markdown) => Self::parse_markdown(body, path),
+            Some(Parser::Notebook) => Self::parse_notebook(body, path),
+            _ => Vec::new(),
         }
+    }
 
+    /// parses the markdown cells of a notebook, line numbers count from the start of the cell
+    ///
+    /// the cells are read like one markdown file, so headings of a cell are part of the prefix
+    /// of tasks in later cells
+    fn parse_notebook(body: &str, path: &Path) -> Vec<Task> {
+        let Some(notebook) = Notebook::parse(body) else {
+            return Vec::new();
+        };
         let mut prefix = PrefixBuffer::new();
         let mut tasks = Vec::new();
-        let body = body.lines().enumerate().map(|(n, l)| (n + 1, l));
-
-        for (line_number, line) in body {
-            if let Some(line) = line.strip_prefix("# ") {
-                Self::handle_heading(line, path, line_number, 1, &mut prefix, &mut tasks);
+        for (i, cell) in notebook.cells(CellType::Markdown) {
+            let lines = cell.source.lines().enumerate().map(|(n, l)| (n + 1, l));
+            for mut task in Self::parse_lines(lines, path, &mut prefix) {
+                task.cell = Some(i);
+                tasks.push(task);
             }
-            if let Some(line) = line.strip_prefix("## ") {
-                Self::handle_heading(line, path, line_number, 2, &mut prefix, &mut tasks);
-            }
-            if let Some(line) = line.strip_prefix("### ") {
-                Self::handle_heading(line, path, line_number, 3, &mut prefix, &mut tasks);
+        }
+        tasks
+    }
+
+    fn parse_markdown(body: &str, path: &Path) -> Vec<Task> {
+        let (front_matter, front_matter_len) = FrontMatter::parse(body);
+        if front_matter.skip_tasks {
+            log::info!("skipping tasks of file: {:?}", path);
+            return Vec::new();
+        }
+
+        let mut prefix = PrefixBuffer::new();
+        if let Some(default_prefix) = front_matter.prefix.as_deref() {
+            prefix.push(0, default_prefix);
+        }
+        let lines = body
+            .lines()
+            .enumerate()
+            .map(|(n, l)| (n + 1, l))
+            .skip(front_matter_len);
+
+        let mut tasks = Self::parse_lines(lines, path, &mut prefix);
+        for task in tasks.iter_mut() {
+            let tags = std::mem::take(&mut task.tags);
+            task.tags = front_matter.tags.clone();
+            task.tags
+                .extend(tags.into_iter().filter(|t| !front_matter.tags.contains(t)));
+            if task.assignees.is_empty() {
+                task.assignees = front_matter.assignees.clone();
             }
-            if let Some(line) = line.strip_prefix("#### ") {
-                Self::handle_heading(line, path, line_number, 4, &mut prefix, &mut tasks);
+        }
+
+        log::info!("parsed tasks from file: {:?}", path);
+        tasks
+    }
+
+    /// parses the tasks of markdown `lines` with their line numbers, `prefix` holds the headings
+    /// which enclose the first line
+    fn parse_lines<'a>(
+        lines: impl Iterator<Item = (usize, &'a str)>,
+        path: &Path,
+        prefix: &mut PrefixBuffer<'a>,
+    ) -> Vec<Task> {
+        let mut tasks: Vec<Task> = Vec::new();
+        let mut in_event = false;
+        // heading level of the task whose body is currently read
+        let mut body_level = None;
+
+        for (line_number, line) in lines {
+            if let Some((level, text)) = heading(line) {
+                let is_task = text.trim().starts_with("TASK:") || text.trim().starts_with("DONE:");
+                in_event = text.trim().starts_with("EVENT:");
+
+                match body_level {
+                    Some(body_level) if level > body_level && !is_task && !in_event => {
+                        if let Some(task) = tasks.last_mut() {
+                            task.body.push_str(line);
+                            task.body.push('\n');
+                        }
+                    }
+                    _ => body_level = None,
+                }
+                if is_task {
+                    body_level = Some(level);
+                }
+
+                Self::handle_heading(text, path, line_number, level, prefix, &mut tasks);
+                continue;
             }
-            if let Some(line) = line.strip_prefix("##### ") {
-                Self::handle_heading(line, path, line_number, 5, &mut prefix, &mut tasks);
+
+            // lines below an event belong to the event and not to the previous task
+            if in_event {
+                continue;
             }
-            if let Some(line) = line.strip_prefix("###### ") {
-                Self::handle_heading(line, path, line_number, 6, &mut prefix, &mut tasks);
+
+            if body_level.is_some()
+                && !is_property(line)
+                && let Some(task) = tasks.last_mut()
+            {
+                task.body.push_str(line);
+                task.body.push('\n');
             }
 
-            if let Some(subtask) = line.strip_prefix("- [X]") {
-                if let Some(task) = tasks.last_mut() {
-                    task.subtasks.push(SubTask::Done(subtask.trim().into()))
-                }
+            if let Some(subtask) = line.strip_prefix("- [X]")
+                && let Some(task) = tasks.last_mut()
+            {
+                task.subtasks.push(SubTask::new(subtask, true))
             }
 
-            if let Some(subtask) = line.strip_prefix("- [ ]") {
-                if let Some(task) = tasks.last_mut() {
-                    task.subtasks.push(SubTask::NotDone(subtask.trim().into()))
-                }
+            if let Some(subtask) = line.strip_prefix("- [ ]")
+                && let Some(task) = tasks.last_mut()
+            {
+                task.subtasks.push(SubTask::new(subtask, false))
             }
 
             if let Some(line) = line.strip_prefix(">") {
                 let line = line.trim();
-                if let Some(deadline) = line.strip_prefix("DEADLINE:") {
+                if let Some(deadline) = line.strip_prefix("DEADLINE:")
+                    && let Ok(relative) = deadline.parse::<Relative>()
+                {
+                    if let Some(task) = tasks.last_mut() {
+                        task.deadline_relative = Some(relative);
+                    }
+                } else if let Some(deadline) = line.strip_prefix("DEADLINE:") {
                     let deadline = match deadline.trim().parse::<Span>() {
                         Ok(deadline) => deadline,
                         Err(err) => {
@@ -246,6 +586,12 @@ impl Task {
                     if let Some(task) = tasks.last_mut() {
                         task.deadline = Some(deadline);
                     }
+                } else if let Some(scheduled) = line.strip_prefix("SCHEDULED:")
+                    && let Ok(relative) = scheduled.parse::<Relative>()
+                {
+                    if let Some(task) = tasks.last_mut() {
+                        task.scheduled_relative = Some(relative);
+                    }
                 } else if let Some(scheduled) = line.strip_prefix("SCHEDULED:") {
                     let scheduled = match scheduled.trim().parse::<Span>() {
                         Ok(scheduled) => scheduled,
@@ -257,6 +603,17 @@ impl Task {
                     if let Some(task) = tasks.last_mut() {
                         task.scheduled = Some(scheduled);
                     }
+                } else if let Some(closed) = line.strip_prefix("CLOSED:") {
+                    let closed = match closed.trim().parse::<Span>() {
+                        Ok(closed) => closed,
+                        Err(err) => {
+                            log::warn!("ignoring parsing error in closed: {:?}", err);
+                            continue;
+                        }
+                    };
+                    if let Some(task) = tasks.last_mut() {
+                        task.closed = Some(closed);
+                    }
                 } else if let Some(work) = line.strip_prefix("WORK:") {
                     let work = match work.trim().parse::<Span>() {
                         Ok(work) => work,
@@ -268,10 +625,74 @@ impl Task {
                     if let Some(task) = tasks.last_mut() {
                         task.work.push(work);
                     }
+                } else if let Some(defer) = line.strip_prefix("DEFER:") {
+                    let defer = match defer.trim().parse::<Date>() {
+                        Ok(defer) => defer,
+                        Err(err) => {
+                            log::warn!("ignoring parsing error in defer: {:?}", err);
+                            continue;
+                        }
+                    };
+                    if let Some(task) = tasks.last_mut() {
+                        task.defer = Some(defer);
+                    }
+                } else if let Some(assignees) = line.strip_prefix("ASSIGNEE:")
+                    && let Some(task) = tasks.last_mut()
+                {
+                    let assignees = assignees
+                        .split(',')
+                        .map(|a| a.trim().trim_start_matches('@'))
+                        .filter(|a| !a.is_empty())
+                        .map(|a| a.into());
+                    task.assignees.extend(assignees);
+                } else if let Some(sources) = line.strip_prefix("SOURCES:")
+                    && let Some(task) = tasks.last_mut()
+                {
+                    sources
+                        .split(',')
+                        .map(|s| s.trim())
+                        .filter(|s| !s.is_empty())
+                        .for_each(|s| task.add_source(s));
+                } else if let Some(tags) = line.strip_prefix("TAGS:")
+                    && let Some(task) = tasks.last_mut()
+                {
+                    let tags = tags
+                        .split(',')
+                        .map(|t| t.trim())
+                        .filter(|t| !t.is_empty())
+                        .map(|t| t.into());
+                    task.tags.extend(tags);
+                } else if let Some(id) = line.strip_prefix("ID:")
+                    && let Some(task) = tasks.last_mut()
+                {
+                    task.id = Some(id.trim().into());
+                } else if let Some(blocked_by) = line.strip_prefix("BLOCKED_BY:")
+                    && let Some(task) = tasks.last_mut()
+                {
+                    let references = blocked_by
+                        .split(',')
+                        .map(|r| r.trim())
+                        .filter(|r| !r.is_empty())
+                        .map(|r| r.into());
+                    task.blocked_by.extend(references);
                 }
             }
         }
-        log::info!("parsed tasks from file: {:?}", path);
+        for task in tasks.iter_mut() {
+            let lines = task.body.lines().collect::<Vec<_>>();
+            let start = lines.iter().take_while(|l| l.trim().is_empty()).count();
+            let end = lines.len()
+                - lines[start..]
+                    .iter()
+                    .rev()
+                    .take_while(|l| l.trim().is_empty())
+                    .count();
+            task.body = lines[start..end].join("\n");
+
+            let body = std::mem::take(&mut task.body);
+            links(&body).into_iter().for_each(|s| task.add_source(s));
+            task.body = body;
+        }
         tasks
     }
 }
@@ -279,6 +700,7 @@ impl Task {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::format::Definition;
 
     #[test]
     fn test_task_parse() {
@@ -295,12 +717,275 @@ mod test {
             prefix: "Uni/Course 1".into(),
             title: "Exercise 1".into(),
             path: path.into(),
-            sources: None,
+            sources: vec![],
             deadline: None,
             scheduled: None,
             line_number: 4,
+            id: None,
+            blocked_by: vec![],
+            defer: None,
+            assignees: vec![],
+            body: String::new(),
+            closed: None,
+            deadline_relative: None,
+            scheduled_relative: None,
+            tags: vec![],
+            cell: None,
+            directory: None,
         }];
 
         assert_eq!(expected, Task::parse(body, path));
     }
+
+    #[test]
+    fn test_task_parse_skips_events() {
+        let path = "/home/to/some/folder.md";
+        let body = "
+# Uni
+## TASK: Exercise 1
+> SCHEDULED: 2025-07-01
+## EVENT: Lecture
+> SCHEDULED: 2025-07-02
+## TASK: Exercise 2
+";
+        let tasks = Task::parse(body, path);
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].scheduled, "2025-07-01".parse().ok());
+        assert_eq!(tasks[1].prefix, "Uni");
+    }
+
+    #[test]
+    fn test_task_parse_dependencies() {
+        let path = "/home/to/some/folder.md";
+        let body = "
+# Uni
+## TASK: Write exam
+> ID: write-exam
+## TASK: Grade exam
+> BLOCKED_BY: write-exam, Uni/Book room
+> BLOCKED_BY: Uni/Print exam
+";
+        let tasks = Task::parse(body, path);
+
+        assert_eq!(tasks[0].id.as_deref(), Some("write-exam"));
+        assert!(tasks[0].blocked_by.is_empty());
+        assert_eq!(
+            tasks[1].blocked_by,
+            vec!["write-exam", "Uni/Book room", "Uni/Print exam"]
+        );
+        assert!(tasks[0].matches("write-exam"));
+        assert!(tasks[1].matches("Uni/Grade exam"));
+        assert!(!tasks[1].matches("Grade exam"));
+    }
+
+    #[test]
+    fn test_task_parse_assignees() {
+        let path = "/home/to/some/folder.md";
+        let body = "
+# TASK: Write report @alice
+> ASSIGNEE: bob, @carol
+# TASK: Review report
+";
+        let tasks = Task::parse(body, path);
+
+        assert_eq!(tasks[0].title, "Write report @alice");
+        assert_eq!(tasks[0].assignees, vec!["alice", "bob", "carol"]);
+        assert!(tasks[0].is_assigned_to("@bob"));
+        assert!(!tasks[0].is_assigned_to("dave"));
+        assert!(tasks[1].assignees.is_empty());
+    }
+
+    #[test]
+    fn test_task_parse_body() {
+        let path = "/home/to/some/folder.md";
+        let body = "
+# Uni
+## TASK: Exercise 1
+> SCHEDULED: 2025-07-01
+
+See [the sheet](https://example.com/sheet.pdf).
+> a quote
+- [ ] solve
+### Notes
+  indented
+
+## TASK: Exercise 2
+> DEADLINE: 2025-07-02
+## Course 2
+This is not part of a task
+";
+        let tasks = Task::parse(body, path);
+
+        assert_eq!(
+            tasks[0].body,
+            "See [the sheet](https://example.com/sheet.pdf).\n> a quote\n### Notes\n  indented"
+        );
+        assert_eq!(tasks[1].body, "");
+
+        let mut task = tasks[0].clone();
+        task.truncate_body(8);
+        assert_eq!(task.body, "See [the");
+    }
+
+    #[test]
+    fn test_task_parse_sources() {
+        let path = "/home/user/notes/uni.md";
+        let body = "
+# TASK: Read paper
+> SOURCES: ../papers/smith2024.pdf, /home/user/slides.pdf
+See [notes](./notes.md#intro), [the site](https://example.com) and
+[the paper](../papers/smith2024.pdf).
+";
+        let task = &Task::parse(body, path)[0];
+        let expected: Vec<PathBuf> = vec![
+            "/home/user/notes/../papers/smith2024.pdf".into(),
+            "/home/user/slides.pdf".into(),
+            "/home/user/notes/notes.md".into(),
+        ];
+
+        assert_eq!(task.sources, expected);
+    }
+
+    #[test]
+    fn test_deserialize_sources() {
+        let task = Task::parse("# TASK: Read paper", "/home/user/uni.md").remove(0);
+        let json = serde_json::to_string(&task).unwrap();
+
+        let old = json.replace("\"sources\":[]", "\"sources\":null");
+        assert_eq!(serde_json::from_str::<Task>(&old).unwrap(), task);
+
+        let old = json.replace("\"sources\":[]", "\"sources\":\"/home/user/a.pdf\"");
+        let got = serde_json::from_str::<Task>(&old).unwrap();
+        assert_eq!(got.sources, vec![PathBuf::from("/home/user/a.pdf")]);
+    }
+
+    #[test]
+    fn test_task_parse_front_matter() {
+        let path = "/home/to/some/folder.md";
+        let body = "---
+owl:
+  prefix: Uni
+  tags: [exam]
+  assignee: alice
+---
+# Course 1
+## TASK: Exercise 1
+> TAGS: course, exam
+# TASK: Exercise 2 @bob
+";
+        let tasks = Task::parse(body, path);
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].heading_path(), "Uni/Course 1/Exercise 1");
+        assert_eq!(tasks[0].line_number, 8);
+        assert_eq!(tasks[0].tags, vec!["exam", "course"]);
+        assert_eq!(tasks[0].assignees, vec!["alice"]);
+        assert_eq!(tasks[1].heading_path(), "Uni/Exercise 2 @bob");
+        assert_eq!(tasks[1].assignees, vec!["bob"]);
+
+        let body = "+++\n[owl]\nskip_tasks = true\n+++\n# TASK: Exercise 1";
+        assert!(Task::parse(body, path).is_empty());
+    }
+
+    #[test]
+    fn test_task_parse_subtasks() {
+        let path = "/home/to/some/folder.md";
+        let body = "
+# TASK: Paper
+- [X] find a title
+- [ ] draft intro @2025-07-03
+- [ ] ask @alice
+- [ ] present @2025-07-10 14:00 - 15:00
+";
+        let task = &Task::parse(body, path)[0];
+        let subtasks = task
+            .subtasks
+            .iter()
+            .map(|s| (s.title.as_str(), s.done, s.date.map(|d| d.to_string())))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            subtasks,
+            vec![
+                ("find a title", true, None),
+                ("draft intro", false, Some("2025-07-03".into())),
+                ("ask @alice", false, None),
+                ("present", false, Some("2025-07-10 14:00 - 15:00".into())),
+            ]
+        );
+        assert_eq!(
+            task.subtasks[1].to_string(),
+            "- [ ] draft intro @2025-07-03"
+        );
+    }
+
+    #[test]
+    fn test_task_parse_notebook() {
+        let body = r###"{
+  "cells": [
+    { "cell_type": "code", "source": "# TASK: not a task" },
+    { "cell_type": "markdown", "source": ["# Analysis\n", "\n", "## TASK: clean data"] }
+  ]
+}"###;
+        let tasks = Task::parse(body, "/home/analysis.ipynb");
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].prefix, "Analysis");
+        assert_eq!(tasks[0].location(), "/home/analysis.ipynb#2:3");
+    }
+
+    #[test]
+    fn test_task_parse_notebook_headings() {
+        let body = r####"{
+  "cells": [
+    { "cell_type": "markdown", "source": "# Project" },
+    { "cell_type": "code", "source": "import pandas" },
+    { "cell_type": "markdown", "source": ["## Data\n", "### TASK: clean data"] },
+    { "cell_type": "markdown", "source": "## TASK: plot" }
+  ]
+}"####;
+        let tasks = Task::parse(body, "/home/analysis.ipynb");
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].heading_path(), "Project/Data/clean data");
+        assert_eq!(tasks[0].location(), "/home/analysis.ipynb#3:2");
+        assert_eq!(tasks[1].heading_path(), "Project/plot");
+    }
+
+    #[test]
+    fn test_task_parse_with_formats() {
+        let definition: Definition = toml::from_str(
+            r#"
+name = "notes"
+extensions = ["notes"]
+parser = "markdown"
+"#,
+        )
+        .unwrap();
+        let formats = Formats::new(vec![definition]);
+        let body = "# Uni\n## TASK: Exam\n";
+
+        let tasks = Task::parse_with(body, "/home/uni.notes", &formats);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].heading_path(), "Uni/Exam");
+        assert!(Task::parse(body, "/home/uni.notes").is_empty());
+    }
+
+    #[test]
+    fn test_task_parse_defer() {
+        let path = "/home/to/some/folder.md";
+        let body = "
+# TASK: Renew contract
+> DEFER: 2025-09-01
+> DEADLINE: 2025-09-30
+";
+        let task = &Task::parse(body, path)[0];
+        let defer = Date::from_ymd(2025, 9, 1).unwrap();
+
+        assert_eq!(task.defer, Some(defer));
+        assert!(task.is_deferred(Date::from_ymd(2025, 8, 31).unwrap()));
+        assert!(!task.is_deferred(defer));
+        assert!(!task.is_deferred(Date::from_ymd(2025, 9, 2).unwrap()));
+    }
 }
@@ -2,6 +2,7 @@ use super::Duration;
 use chrono::Datelike;
 use chrono::NaiveDate;
 use std::cmp::Ordering;
+use std::fmt::Display;
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
@@ -27,6 +28,12 @@ const MONTH_LITERALS: [&str; 13] = [
     "", "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
 ];
 
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:>04}-{:>02}-{:>02}", self.year, self.month, self.day)
+    }
+}
+
 impl PartialOrd for Date {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -117,13 +124,13 @@ impl Date {
 }
 
 fn is_leap_year(year: u16) -> bool {
-    if year % 400 == 0 {
+    if year.is_multiple_of(400) {
         return true;
     }
-    if year % 100 == 0 {
+    if year.is_multiple_of(100) {
         return false;
     }
-    if year % 4 == 0 {
+    if year.is_multiple_of(4) {
         return true;
     }
     false
@@ -211,12 +218,19 @@ mod test {
             "2024-02-28".parse::<Date>().unwrap(),
             Date {
                 year: 2024,
-                month: 02,
+                month: 2,
                 day: 28,
             }
         );
     }
 
+    #[test]
+    fn test_display_date() {
+        let date = Date::from_ymd(2024, 2, 9).unwrap();
+        assert_eq!(date.to_string(), "2024-02-09");
+        assert_eq!(date.to_string().parse::<Date>().unwrap(), date);
+    }
+
     #[test]
     fn test_parse_date_invalid() {
         assert!("2025-02-29".parse::<Date>().is_err());
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum Duration {
     Day(u64),
     Week(u64),
@@ -14,15 +14,32 @@ impl FromStr for Duration {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.trim();
-        let num = &s[..s.len() - 1];
-        let num: u64 = num.parse().ok().ok_or(())?;
+        let (index, unit) = s.char_indices().next_back().ok_or(())?;
+        let num: u64 = s[..index].parse().ok().ok_or(())?;
 
-        match s.chars().last() {
-            Some('d') => Ok(Self::Day(num)),
-            Some('w') => Ok(Self::Week(num)),
-            Some('m') => Ok(Self::Month(num)),
-            Some('y') => Ok(Self::Year(num)),
+        match unit {
+            'd' => Ok(Self::Day(num)),
+            'w' => Ok(Self::Week(num)),
+            'm' => Ok(Self::Month(num)),
+            'y' => Ok(Self::Year(num)),
             _ => Err(()),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("2d".parse(), Ok(Duration::Day(2)));
+        assert_eq!(" 10w ".parse(), Ok(Duration::Week(10)));
+        assert_eq!("1y".parse(), Ok(Duration::Year(1)));
+        assert_eq!("".parse::<Duration>(), Err(()));
+        assert_eq!("2h".parse::<Duration>(), Err(()));
+        assert_eq!("d".parse::<Duration>(), Err(()));
+        assert_eq!("2ä".parse::<Duration>(), Err(()));
+        assert_eq!("ä".parse::<Duration>(), Err(()));
+    }
+}
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::fmt::Display;
 use std::str::FromStr;
 
 use super::{ClockTime, Date};
@@ -44,6 +45,19 @@ impl FromStr for Span {
     }
 }
 
+impl Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.date)?;
+        if let Some(start) = self.start {
+            write!(f, " {}", start)?;
+        }
+        if let Some(end) = self.end {
+            write!(f, " - {}", end)?;
+        }
+        Ok(())
+    }
+}
+
 impl Span {
     /// contructs a new Span instance
     pub fn new(date: Date, start: Option<ClockTime>, end: Option<ClockTime>) -> Self {
@@ -115,4 +129,11 @@ mod test {
 
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn test_display() {
+        for span in ["2024-12-13", "2024-12-13 12:00", "2024-12-13 12:00 - 14:30"] {
+            assert_eq!(span.parse::<Span>().unwrap().to_string(), span);
+        }
+    }
 }
@@ -1,11 +1,108 @@
-use crate::format::Format;
+use crate::comment::{self, Comment, Syntax};
+use crate::format::{Formats, Parser};
+use crate::frontmatter::FrontMatter;
+use crate::notebook::{CellType, Notebook};
+use crate::symbol::{self, Scopes};
+use crate::time::Date;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+/// how urgent a todo with a certain marker is
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    #[default]
+    Warning,
+    Error,
+}
+
+/// a keyword like `TODO` or `FIXME` which starts a todo when it is followed by a `:`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Marker {
+    pub name: String,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+impl Marker {
+    pub fn new(name: &str, severity: Severity) -> Self {
+        Self {
+            name: name.into(),
+            severity,
+        }
+    }
+}
+
+/// the markers which are recognised if the config does not list any
+pub fn default_markers() -> Vec<Marker> {
+    vec![
+        Marker::new("TODO", Severity::Info),
+        Marker::new("HACK", Severity::Warning),
+        Marker::new("XXX", Severity::Warning),
+        Marker::new("FIXME", Severity::Error),
+        Marker::new("BUG", Severity::Error),
+    ]
+}
+
+/// settings of the todo parser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub markers: Vec<Marker>,
+    pub formats: Formats,
+    /// whether markers in the middle of paragraphs of markup formats like markdown start todos
+    pub inline: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            markers: default_markers(),
+            formats: Formats::default(),
+            inline: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Todo {
     pub title: String,
     pub line_number: usize,
+    /// last line of the todo, todos which continue over several comment lines end after
+    /// `line_number`
+    pub end_line_number: usize,
     pub path: PathBuf,
+    /// index of the notebook cell starting at `1`, line numbers count from the start of the cell
+    pub cell: Option<usize>,
+    /// continuation lines of a todo which spans several comment lines
+    pub body: String,
+    /// function, class or impl which encloses the todo, eg. `Task::parse`
+    pub symbol: Option<String>,
+    /// name of the marker which started the todo, eg. `FIXME`
+    pub kind: String,
+    /// person responsible for the todo as in `TODO(alice): ...`
+    pub owner: Option<String>,
+    /// issue the todo refers to as in `TODO(#123): ...`
+    pub issue: Option<String>,
+    /// date the todo is due as in `TODO(2025-08-01): ...`
+    pub due: Option<Date>,
+    /// id of the task the todo has been promoted to as in `TODO(task:fix-parser): ...`
+    pub task: Option<String>,
+    /// author of the commit which last changed the line of the todo, see `git::blame`
+    pub author: Option<String>,
+    /// date of the commit which last changed the line of the todo
+    pub committed: Option<Date>,
 }
 
 impl Todo {
@@ -13,122 +110,523 @@ impl Todo {
         Self {
             title: title.into(),
             line_number,
+            end_line_number: line_number,
             path: path.into(),
+            cell: None,
+            body: String::new(),
+            symbol: None,
+            author: None,
+            committed: None,
+            task: None,
+            kind: "TODO".into(),
+            owner: None,
+            issue: None,
+            due: None,
+        }
+    }
+
+    /// returns `path:line` or `path#cell:line` for todos in notebooks
+    pub fn location(&self) -> String {
+        let path = self.path.to_str().unwrap();
+        match self.cell {
+            Some(cell) => format!("{}#{}:{}", path, cell, self.line_number),
+            None => format!("{}:{}", path, self.line_number),
+        }
+    }
+
+    /// checks whether `name` owns the todo, a leading `@` is ignored like in annotations
+    pub fn is_owned_by(&self, name: &str) -> bool {
+        let name = name.trim().trim_start_matches('@');
+        self.owner.as_deref() == Some(name)
+    }
+
+    /// adds `line` at `line_number` to the body of the todo
+    fn push_line(&mut self, line: &str, line_number: usize) {
+        if !self.body.is_empty() {
+            self.body.push('\n');
+        }
+        self.body.push_str(line);
+        self.end_line_number = line_number;
+    }
+
+    fn with_kind(mut self, kind: &str) -> Self {
+        self.kind = kind.into();
+        self
+    }
+
+    /// sets the fields described by the comma separated parts of an annotation like
+    /// `TODO(alice, #123, 2025-08-01): ...`
+    fn with_annotation(mut self, annotation: &str) -> Self {
+        for part in annotation
+            .split(',')
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+        {
+            if let Ok(due) = part.parse::<Date>() {
+                self.due = Some(due);
+            } else if let Some(task) = part.strip_prefix("task:") {
+                self.task = Some(task.trim().into());
+            } else if is_issue(part) {
+                self.issue = Some(part.into());
+            } else {
+                self.owner = Some(part.trim_start_matches('@').into());
+            }
+        }
+        self
+    }
+}
+
+/// checks whether `part` of an annotation refers to an issue, eg. `#123`, `GH-123`, `PROJ-42` or
+/// the url of an issue
+fn is_issue(part: &str) -> bool {
+    if part.starts_with('#') || part.contains("://") {
+        return true;
+    }
+    match part.split_once('-') {
+        Some((project, number)) => {
+            !project.is_empty()
+                && project.chars().all(|c| c.is_ascii_uppercase())
+                && !number.is_empty()
+                && number.chars().all(|c| c.is_ascii_digit())
         }
+        None => false,
     }
 }
 
+/// parses the todos of a file with the default `Options`
 pub fn parse<P: AsRef<Path>>(body: &str, path: P) -> Vec<Todo> {
+    parse_with(body, path, &Options::default())
+}
+
+/// parses the todos of a file, the way todos are written depends on the format of the file
+pub fn parse_with<P: AsRef<Path>>(body: &str, path: P, options: &Options) -> Vec<Todo> {
     let path: &Path = path.as_ref();
-    match Format::new(path) {
-        Format::Unknown => Vec::new(),
-        Format::Markdown => parse_md(body, path),
-        Format::Typst => parse_typst(body, path),
-        Format::Latex => parse_latex(body, path),
-
-        Format::Shell | Format::Python | Format::Nix => parse_shell_like(body, path),
-
-        // parse c like programming languages
-        Format::C
-        | Format::JavaScript
-        | Format::Go
-        | Format::Java
-        | Format::CPP
-        | Format::Rust
-        | Format::Typescript
-        | Format::CSharp => parse_clike(body, path),
-    }
-}
-
-fn parse_latex(body: &str, path: &Path) -> Vec<Todo> {
-    body.lines()
-        .enumerate()
-        .map(|(n, l)| (n + 1, l.trim()))
-        .filter(|(_, l)| l.starts_with("% TODO:"))
-        .map(|(n, l)| (n, &l[7..]))
-        .map(|(n, l)| (n, l.trim()))
-        .map(|(n, l)| Todo::new(l, n, path))
-        .collect()
-}
-
-fn parse_shell_like(body: &str, path: &Path) -> Vec<Todo> {
-    body.lines()
-        .enumerate()
-        .map(|(n, l)| (n + 1, l.trim()))
-        .filter(|(_, l)| l.starts_with("# TODO:"))
-        .map(|(n, l)| (n, &l[7..]))
-        .map(|(n, l)| (n, l.trim()))
-        .map(|(n, l)| Todo::new(l, n, path))
-        .collect()
-}
-
-fn parse_typst(body: &str, path: &Path) -> Vec<Todo> {
-    let mut todos = Vec::new();
-    for (line_number, line) in body.lines().enumerate() {
-        let line_number = line_number + 1;
-        if let Some(title) = line.trim().strip_prefix("- TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
+    let markers = &options.markers[..];
+    let Some(format) = options.formats.detect(path, body) else {
+        return Vec::new();
+    };
+    // only markup files have front matter, other files may start with `---` by chance
+    if matches!(format.parser, Parser::Markdown | Parser::Typst)
+        && FrontMatter::parse(body).0.skip_todos
+    {
+        log::info!("skipping todos of file: {:?}", path);
+        return Vec::new();
+    }
+    match format.parser {
+        Parser::Markdown => parse_md(body, path, &format.syntax, options),
+        Parser::Typst => parse_typst(body, path, &format.syntax, options),
+        Parser::Latex => parse_latex(body, path, markers),
+        Parser::Notebook => parse_notebook(body, path, options),
+
+        // parse programming languages by their comments
+        Parser::Code => {
+            let todos = parse_code(body, path, &format.syntax, markers);
+            with_symbols(todos, body, format.scopes)
         }
-        if let Some(title) = line.strip_prefix("= TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
+    }
+}
+
+/// sets the symbol enclosing each of the `todos` in `body`
+fn with_symbols(mut todos: Vec<Todo>, body: &str, scopes: Scopes) -> Vec<Todo> {
+    if scopes == Scopes::None || todos.is_empty() {
+        return todos;
+    }
+    let symbols = symbol::symbols(body, scopes);
+    for todo in todos.iter_mut() {
+        todo.symbol = symbols.get(todo.line_number - 1).cloned().flatten();
+    }
+    todos
+}
+
+/// a todo found in a line by `strip_marker`
+struct Match<'a> {
+    marker: &'a Marker,
+    annotation: Option<&'a str>,
+    title: &'a str,
+}
+
+impl Match<'_> {
+    fn into_todo(self, line_number: usize, path: &Path) -> Todo {
+        let todo = Todo::new(self.title, line_number, path).with_kind(&self.marker.name);
+        match self.annotation {
+            Some(annotation) => todo.with_annotation(annotation),
+            None => todo,
         }
-        if let Some(title) = line.strip_prefix("== TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
+    }
+}
+
+/// matches `text` against `<MARKER>: <title>` or `<MARKER>(<annotation>): <title>`
+fn strip_marker<'a>(text: &'a str, markers: &'a [Marker]) -> Option<Match<'a>> {
+    let text = text.trim_start();
+    markers.iter().find_map(|marker| {
+        let rest = text.strip_prefix(marker.name.as_str())?;
+        let (annotation, rest) = match rest.strip_prefix('(') {
+            Some(rest) => {
+                let (annotation, rest) = rest.split_once(')')?;
+                (Some(annotation), rest)
+            }
+            None => (None, rest),
+        };
+        let title = rest.strip_prefix(':')?;
+        Some(Match {
+            marker,
+            annotation,
+            title: title.trim(),
+        })
+    })
+}
+
+/// parses todos in `comments`, a todo continues over the following comment lines which are
+/// indented further than its marker
+///
+/// ```c
+/// // TODO: refactor this because
+/// //       it allocates per call
+/// ```
+fn parse_comments(comments: &[Comment], path: &Path, markers: &[Marker]) -> Vec<Todo> {
+    let mut todos = Vec::new();
+    let mut comments = comments.iter().peekable();
+
+    while let Some(comment) = comments.next() {
+        let Some(todo) = strip_marker(comment.text, markers) else {
+            continue;
+        };
+        let mut todo = todo.into_todo(comment.line_number, path);
+
+        while let Some(next) = comments.next_if(|next| {
+            next.line_number == todo.end_line_number + 1
+                && next.indent > comment.indent
+                && !next.text.is_empty()
+                && strip_marker(next.text, markers).is_none()
+        }) {
+            todo.push_line(next.text, next.line_number);
         }
-        if let Some(title) = line.strip_prefix("=== TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
+        todos.push(todo);
+    }
+    todos
+}
+
+/// environments whose content is not parsed
+const LATEX_VERBATIM: [&str; 6] = [
+    "comment",
+    "verbatim",
+    "verbatim*",
+    "Verbatim",
+    "lstlisting",
+    "minted",
+];
+
+/// macros of the todonotes package which are turned into todos and the prefix of their title
+const LATEX_MACROS: [(&str, &str); 2] = [("\\todo", ""), ("\\missingfigure", "missing figure: ")];
+
+/// splits a line of LaTeX into the text before and after the first `%` which is not escaped
+fn latex_split_comment(line: &str) -> (&str, Option<&str>) {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '\\' => escaped = !escaped,
+            '%' if !escaped => return (&line[..i], Some(&line[i + 1..])),
+            _ => escaped = false,
         }
-        if let Some(title) = line.strip_prefix("==== TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
+    }
+    (line, None)
+}
+
+/// returns the index after the group which starts at `start` with `open` and ends with the
+/// matching `close`
+fn latex_group_end(text: &str, start: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, c) in text[start..].char_indices() {
+        match c {
+            '\\' => {
+                escaped = !escaped;
+                continue;
+            }
+            c if c == open && !escaped => depth += 1,
+            c if c == close && !escaped => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + i + c.len_utf8());
+                }
+            }
+            _ => (),
         }
-        if let Some(title) = line.strip_prefix("===== TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
+        escaped = false;
+    }
+    None
+}
+
+/// parses `\todo[<options>]{<text>}` and `\missingfigure[<options>]{<text>}` in `code`, which may
+/// span several lines
+fn parse_latex_macros(code: &str, path: &Path) -> Vec<Todo> {
+    let line_number = |i: usize| code[..i].matches('\n').count() + 1;
+    let mut todos = Vec::new();
+
+    for (name, title_prefix) in LATEX_MACROS {
+        for (start, _) in code.match_indices(name) {
+            let mut i = start + name.len();
+            if code[i..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+                continue;
+            }
+
+            i += comment::indent(&code[i..]);
+            if code[i..].starts_with('[') {
+                let Some(end) = latex_group_end(code, i, '[', ']') else {
+                    continue;
+                };
+                i = end + comment::indent(&code[end..]);
+            }
+            if !code[i..].starts_with('{') {
+                continue;
+            }
+            let Some(end) = latex_group_end(code, i, '{', '}') else {
+                log::warn!("ignoring unclosed {} in: {:?}", name, path);
+                continue;
+            };
+
+            let text = code[i + 1..end - 1].split_whitespace().collect::<Vec<_>>();
+            let title = format!("{}{}", title_prefix, text.join(" "));
+            let mut todo = Todo::new(title.trim(), line_number(start), path);
+            todo.end_line_number = line_number(end - 1);
+            todos.push(todo);
         }
-        if let Some(title) = line.strip_prefix("====== TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
+    }
+    todos
+}
+
+/// parses todos in `%` comments and the macros of the todonotes package, comment and verbatim
+/// environments are skipped
+fn parse_latex(body: &str, path: &Path, markers: &[Marker]) -> Vec<Todo> {
+    let mut comments = Vec::new();
+    // the body without comments and skipped environments, lines are kept to keep line numbers
+    let mut code = String::with_capacity(body.len());
+    let mut skip: Option<String> = None;
+
+    for (line_number, line) in body.lines().enumerate() {
+        let line_number = line_number + 1;
+
+        // a line may end and begin several skipped environments like
+        // `\begin{verbatim}a\end{verbatim} % TODO: b`
+        let mut rest = line;
+        loop {
+            if let Some(end) = skip.as_deref() {
+                let Some(i) = rest.find(end) else {
+                    break;
+                };
+                rest = &rest[i + end.len()..];
+                skip = None;
+            }
+
+            let (text, comment) = latex_split_comment(rest);
+            let begin = LATEX_VERBATIM
+                .iter()
+                .filter_map(|env| {
+                    let begin = format!("\\begin{{{}}}", env);
+                    text.find(&begin).map(|i| (i, i + begin.len(), env))
+                })
+                .min();
+            if let Some((start, end, env)) = begin {
+                code.push_str(&text[..start]);
+                skip = Some(format!("\\end{{{}}}", env));
+                rest = &rest[end..];
+                continue;
+            }
+
+            if let Some(comment) = comment {
+                comments.push(Comment {
+                    line_number,
+                    text: comment.trim(),
+                    indent: comment::indent(comment),
+                });
+            }
+            code.push_str(text);
+            break;
         }
+        code.push('\n');
     }
+
+    let mut todos = parse_comments(&comments, path, markers);
+    todos.extend(parse_latex_macros(&code, path));
+    todos.sort_by_key(|todo| todo.line_number);
     todos
 }
 
-fn parse_md(body: &str, path: &Path) -> Vec<Todo> {
+/// matches the first word of `text` which starts a todo like `see above TODO: fix`
+fn find_marker<'a>(text: &'a str, markers: &'a [Marker]) -> Option<Match<'a>> {
+    std::iter::once(0)
+        .chain(
+            text.char_indices()
+                .filter(|(_, c)| c.is_whitespace())
+                .map(|(i, c)| i + c.len_utf8()),
+        )
+        .find_map(|i| strip_marker(&text[i..], markers))
+}
+
+/// parses todos in headings and list items, `heading` is the character used for headings
+///
+/// if `options.inline` is set todos in other lines are found too, except in `commented` lines
+/// whose todos are found by parsing comments
+fn parse_markup(
+    body: &str,
+    path: &Path,
+    heading: char,
+    options: &Options,
+    commented: &HashSet<usize>,
+) -> Vec<Todo> {
+    let markers = &options.markers[..];
     let mut todos = Vec::new();
     for (line_number, line) in body.lines().enumerate() {
         let line_number = line_number + 1;
-        if let Some(title) = line.trim().strip_prefix("- TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
-        }
-        if let Some(title) = line.strip_prefix("# TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
-        }
-        if let Some(title) = line.strip_prefix("## TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
-        }
-        if let Some(title) = line.strip_prefix("### TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
-        }
-        if let Some(title) = line.strip_prefix("#### TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
-        }
-        if let Some(title) = line.strip_prefix("##### TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
-        }
-        if let Some(title) = line.strip_prefix("###### TODO:") {
-            todos.push(Todo::new(title.trim(), line_number, path));
+
+        let level = line.chars().take_while(|c| *c == heading).count();
+        let todo = if let Some(text) = line.trim().strip_prefix("- ") {
+            strip_marker(text, markers)
+        } else if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+            strip_marker(&line[level + 1..], markers)
+        } else if options.inline && !commented.contains(&line_number) {
+            find_marker(line, markers)
+        } else {
+            continue;
+        };
+
+        if let Some(todo) = todo {
+            todos.push(todo.into_todo(line_number, path));
         }
     }
     todos
 }
 
-fn parse_clike(body: &str, path: &Path) -> Vec<Todo> {
-    body.lines()
-        .enumerate()
-        .map(|(n, l)| (n + 1, l.trim()))
-        .filter(|(_, l)| l.starts_with("// TODO:"))
-        .map(|(n, l)| (n, &l[8..]))
-        .map(|(n, l)| (n, l.trim()))
-        .map(|(n, l)| Todo::new(l, n, path))
-        .collect()
+/// parses todos in the markup and in the comments of markup formats
+fn parse_markup_and_comments(
+    body: &str,
+    path: &Path,
+    heading: char,
+    syntax: &Syntax,
+    options: &Options,
+) -> Vec<Todo> {
+    let comments = comment::comments(body, syntax);
+    let commented = comments
+        .iter()
+        .map(|c| c.line_number)
+        .collect::<HashSet<_>>();
+
+    let mut todos = parse_comments(&comments, path, &options.markers);
+    todos.extend(parse_markup(body, path, heading, options, &commented));
+    todos.sort_by_key(|todo| todo.line_number);
+    todos
+}
+
+/// parses todos in headings, list items and comments
+fn parse_typst(body: &str, path: &Path, syntax: &Syntax, options: &Options) -> Vec<Todo> {
+    parse_markup_and_comments(body, path, '=', syntax, options)
+}
+
+/// parses todos in headings, list items and HTML comments
+fn parse_md(body: &str, path: &Path, syntax: &Syntax, options: &Options) -> Vec<Todo> {
+    parse_markup_and_comments(body, path, '#', syntax, options)
+}
+
+/// parses todos in line, block and doc comments, including comments after code on the same line
+fn parse_code(body: &str, path: &Path, syntax: &Syntax, markers: &[Marker]) -> Vec<Todo> {
+    parse_comments(&comment::comments(body, syntax), path, markers)
+}
+
+/// parses markdown cells like markdown files and code cells like files of the kernel language
+fn parse_notebook(body: &str, path: &Path, options: &Options) -> Vec<Todo> {
+    let Some(notebook) = Notebook::parse(body) else {
+        return Vec::new();
+    };
+    let markers = &options.markers[..];
+
+    let language = notebook
+        .language
+        .as_deref()
+        .and_then(|language| options.formats.by_name(language))
+        .filter(|format| format.parser == Parser::Code);
+    if language.is_none() {
+        log::info!("ignoring code cells of notebook: {:?}", path);
+    }
+
+    let markdown_syntax = options
+        .formats
+        .by_name("markdown")
+        .map(|format| format.syntax.clone())
+        .unwrap_or_default();
+    let markdown = notebook
+        .cells(CellType::Markdown)
+        .map(|(i, cell)| (i, parse_md(&cell.source, path, &markdown_syntax, options)));
+    let code = notebook
+        .cells(CellType::Code)
+        .filter_map(|(i, cell)| Some((i, cell, language?)))
+        .map(|(i, cell, format)| {
+            let todos = parse_code(&cell.source, path, &format.syntax, markers);
+            (i, with_symbols(todos, &cell.source, format.scopes))
+        });
+
+    let mut todos = markdown
+        .chain(code)
+        .flat_map(|(i, todos)| {
+            todos.into_iter().map(move |mut todo| {
+                todo.cell = Some(i);
+                todo
+            })
+        })
+        .collect::<Vec<_>>();
+    todos.sort_by_key(|todo| (todo.cell, todo.line_number));
+    todos
+}
+
+impl Todo {
+    /// checks whether two todos say the same, their location and git metadata are ignored so
+    /// that moved todos are still the same
+    fn same_content(&self, other: &Todo) -> bool {
+        self.path == other.path
+            && self.kind == other.kind
+            && self.title == other.title
+            && self.body == other.body
+            && self.owner == other.owner
+            && self.issue == other.issue
+            && self.due == other.due
+    }
+}
+
+/// returns the todos which are only in `old` and the todos which are only in `new`, todos are
+/// matched by their content instead of their line number
+///
+/// # Example
+/// ```
+/// use owl::todo::{diff, parse};
+/// let old = parse("// TODO: keep\n// TODO: remove\n", "main.rs");
+/// let new = parse("// TODO: add\n\n// TODO: keep\n", "main.rs");
+///
+/// let (removed, added) = diff(&old, &new);
+/// assert_eq!(removed[0].title, "remove");
+/// assert_eq!(added[0].title, "add");
+/// assert_eq!(added.len(), 1);
+/// ```
+pub fn diff<'a>(old: &'a [Todo], new: &'a [Todo]) -> (Vec<&'a Todo>, Vec<&'a Todo>) {
+    let mut matched = vec![false; new.len()];
+    let mut removed = Vec::new();
+
+    for todo in old {
+        let same = new
+            .iter()
+            .enumerate()
+            .position(|(i, other)| !matched[i] && todo.same_content(other));
+        match same {
+            Some(i) => matched[i] = true,
+            None => removed.push(todo),
+        }
+    }
+
+    let added = new
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|(todo, _)| todo)
+        .collect();
+    (removed, added)
 }
 
 #[cfg(test)]
@@ -154,7 +652,10 @@ print("Hello World")
         let path: &Path = "/home/main.py".as_ref();
 
         let expected = vec![
-            Todo::new("this is slow", 5, path),
+            Todo {
+                symbol: Some("fib".into()),
+                ..Todo::new("this is slow", 5, path)
+            },
             Todo::new("learn an actual language", 11, path),
         ];
         let got = parse(body, path);
@@ -180,7 +681,10 @@ int main() {
 
         let expected = vec![
             Todo::new("code hashmap", 4, path),
-            Todo::new("error handling", 9, path),
+            Todo {
+                symbol: Some("main".into()),
+                ..Todo::new("error handling", 9, path)
+            },
         ];
 
         let got = parse(body, path);
@@ -188,6 +692,159 @@ int main() {
         assert_eq!(expected, got);
     }
 
+    #[test]
+    fn test_parse_clike_comments() {
+        let body = r#"
+/* TODO: block comment */
+/**
+ * FIXME: inside a doc block
+ */
+/// TODO: doc comment
+//! TODO: inner doc comment
+let s = "// TODO: inside a string";
+foo(); // TODO: trailing
+"#;
+        let path: &Path = "/home/lib.rs".as_ref();
+
+        let expected = vec![
+            Todo::new("block comment", 2, path),
+            Todo::new("inside a doc block", 4, path).with_kind("FIXME"),
+            Todo::new("doc comment", 6, path),
+            Todo::new("inner doc comment", 7, path),
+            Todo::new("trailing", 9, path),
+        ];
+        assert_eq!(expected, parse(body, path));
+    }
+
+    #[test]
+    fn test_parse_multiline() {
+        let body = r#"
+// TODO: refactor this because
+//       it allocates per call
+//       and is slow
+// not part of the todo
+/* FIXME: block
+ *   continued
+ * not continued */
+# TODO: not continued
+"#;
+        let path: &Path = "/home/lib.rs".as_ref();
+        let todos = parse(body, path);
+
+        let ranges = todos
+            .iter()
+            .map(|t| {
+                (
+                    t.title.as_str(),
+                    t.body.as_str(),
+                    t.line_number,
+                    t.end_line_number,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            vec![
+                (
+                    "refactor this because",
+                    "it allocates per call\nand is slow",
+                    2,
+                    4
+                ),
+                ("block", "continued", 6, 7),
+            ]
+        );
+
+        let body = "# TODO: first\n#   second\n# TODO: third\n#   fourth\n";
+        let todos = parse(body, "/home/main.py");
+        assert_eq!(todos[0].body, "second");
+        assert_eq!(todos[1].body, "fourth");
+        assert_eq!(todos[1].end_line_number, 4);
+    }
+
+    #[test]
+    fn test_parse_languages() {
+        let titles = |body: &str, path: &str| {
+            parse(body, path)
+                .into_iter()
+                .map(|t| (t.line_number, t.title))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            titles(
+                "local x = 1 -- TODO: lua\n--[[ FIXME: block ]]",
+                "/init.lua"
+            ),
+            vec![(1, "lua".into()), (2, "block".into())]
+        );
+        assert_eq!(
+            titles(
+                "main = pure () -- TODO: haskell\n{- TODO: block -}",
+                "/Main.hs"
+            ),
+            vec![(1, "haskell".into()), (2, "block".into())]
+        );
+        assert_eq!(
+            titles("<p>TODO: text</p>\n<!-- TODO: html -->", "/index.html"),
+            vec![(2, "html".into())]
+        );
+        assert_eq!(
+            titles("key = \"# TODO: string\" # TODO: toml", "/Cargo.toml"),
+            vec![(1, "toml".into())]
+        );
+        assert_eq!(
+            titles("all:\n\t# TODO: make\n", "/src/Makefile"),
+            vec![(2, "make".into())]
+        );
+        assert_eq!(
+            titles("#!/usr/bin/env bash\n# TODO: script\n", "/bin/deploy"),
+            vec![(2, "script".into())]
+        );
+        assert_eq!(titles("# TODO: no shebang\n", "/bin/deploy"), vec![]);
+        assert_eq!(
+            titles("echo don't\n# TODO: apostrophe\n", "/run.sh"),
+            vec![(2, "apostrophe".into())]
+        );
+        assert_eq!(
+            titles(
+                "<input accept=\"image/*\">\n<p>Don't</p>\n<!-- TODO: vue -->",
+                "/App.vue"
+            ),
+            vec![(3, "vue".into())]
+        );
+        assert_eq!(
+            titles("RUN echo it's\n# TODO: docker\n", "/Dockerfile"),
+            vec![(2, "docker".into())]
+        );
+    }
+
+    #[test]
+    fn test_parse_notebook() {
+        let body = r##"{
+  "cells": [
+    { "cell_type": "markdown", "source": ["# Analysis\n", "- TODO: describe data"] },
+    { "cell_type": "code", "source": ["import pandas\n", "df = load() # FIXME: slow"] },
+    { "cell_type": "raw", "source": "# TODO: ignored" }
+  ],
+  "metadata": { "kernelspec": { "name": "python3", "language": "python" } }
+}"##;
+        let path: &Path = "/home/analysis.ipynb".as_ref();
+        let todos = parse(body, path);
+
+        let locations = todos
+            .iter()
+            .map(|t| (t.location(), t.title.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            locations,
+            vec![
+                ("/home/analysis.ipynb#1:2".into(), "describe data"),
+                ("/home/analysis.ipynb#2:2".into(), "slow"),
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_typst() {
         let path: &Path = "/home/main.typ".as_ref();
@@ -252,6 +909,186 @@ This is a normal line
         assert_eq!(expected, got);
     }
 
+    #[test]
+    fn test_parse_md_comments() {
+        let body = r#"
+# Notes
+<!-- TODO: add screenshot -->
+Some text <!-- FIXME: inline comment -->
+<!--
+  TODO: a comment which
+        spans several lines
+-->
+A paragraph which TODO: is only found inline
+"#;
+        let path: &Path = "/home/notes.md".as_ref();
+
+        let expected = vec![
+            Todo::new("add screenshot", 3, path),
+            Todo::new("inline comment", 4, path).with_kind("FIXME"),
+            {
+                let mut todo = Todo::new("a comment which", 6, path);
+                todo.push_line("spans several lines", 7);
+                todo
+            },
+        ];
+        assert_eq!(expected, parse(body, path));
+
+        let options = Options {
+            inline: true,
+            ..Default::default()
+        };
+        let todos = parse_with(body, path, &options);
+        assert_eq!(todos.len(), 4);
+        assert_eq!(todos[3].title, "is only found inline");
+        assert_eq!(todos[3].line_number, 9);
+    }
+
+    #[test]
+    fn test_diff() {
+        let path = "/home/lib.rs";
+        let old = parse(
+            "// TODO: same\n// TODO: same\n// TODO(#1): annotated\n// FIXME: kind\n",
+            path,
+        );
+        let new = parse(
+            "fn f() {}\n// TODO: same\n// TODO(#2): annotated\n// TODO: kind\n",
+            path,
+        );
+
+        let (removed, added) = diff(&old, &new);
+        let titles = |todos: Vec<&Todo>| {
+            todos
+                .iter()
+                .map(|todo| (todo.line_number, todo.kind.clone(), todo.title.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            titles(removed),
+            vec![
+                (2, "TODO".into(), "same".into()),
+                (3, "TODO".into(), "annotated".into()),
+                (4, "FIXME".into(), "kind".into()),
+            ]
+        );
+        assert_eq!(
+            titles(added),
+            vec![
+                (3, "TODO".into(), "annotated".into()),
+                (4, "TODO".into(), "kind".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_symbols() {
+        let body = r#"
+// TODO: top level
+impl Task {
+    pub fn parse(body: &str) -> Vec<Task> {
+        // TODO: in parse
+    }
+}
+"#;
+        let todos = parse(body, "/home/task.rs");
+        assert_eq!(todos[0].symbol, None);
+        assert_eq!(todos[1].symbol.as_deref(), Some("Task::parse"));
+
+        let body = "class Model:\n    def fit(self):\n        pass  # TODO: implement\n";
+        let todos = parse(body, "/home/model.py");
+        assert_eq!(todos[0].symbol.as_deref(), Some("Model.fit"));
+
+        let todos = parse("key = 1 # TODO: no symbols\n", "/home/config.toml");
+        assert_eq!(todos[0].symbol, None);
+    }
+
+    #[test]
+    fn test_parse_markers() {
+        let body = r#"
+// FIXME: crashes on empty input
+// HACK:works around a compiler bug
+// TODOS: not a marker
+// FOO: unknown marker
+int x = 0; // TODO: after code
+"#;
+        let path: &Path = "/home/main.c".as_ref();
+
+        let expected = vec![
+            Todo::new("crashes on empty input", 2, path).with_kind("FIXME"),
+            Todo::new("works around a compiler bug", 3, path).with_kind("HACK"),
+            Todo::new("after code", 6, path),
+        ];
+        assert_eq!(expected, parse(body, path));
+
+        let options = Options {
+            markers: vec![Marker::new("FOO", Severity::Error)],
+            ..Default::default()
+        };
+        let expected = vec![Todo::new("unknown marker", 5, path).with_kind("FOO")];
+        assert_eq!(expected, parse_with(body, path, &options));
+    }
+
+    #[test]
+    fn test_parse_annotations() {
+        let body = r#"
+// TODO(alice): ask about the api
+// TODO(#123): blocked upstream
+// FIXME(2025-08-01): remove shim
+// TODO(bob, PROJ-42, 2025-09-01): everything
+// TODO(unclosed: not a todo
+"#;
+        let path: &Path = "/home/main.rs".as_ref();
+        let todos = parse(body, path);
+
+        let annotations = todos
+            .iter()
+            .map(|t| {
+                (
+                    t.title.as_str(),
+                    t.owner.as_deref(),
+                    t.issue.as_deref(),
+                    t.due.map(|d| d.to_string()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            annotations,
+            vec![
+                ("ask about the api", Some("alice"), None, None),
+                ("blocked upstream", None, Some("#123"), None),
+                ("remove shim", None, None, Some("2025-08-01".into())),
+                (
+                    "everything",
+                    Some("bob"),
+                    Some("PROJ-42"),
+                    Some("2025-09-01".into())
+                ),
+            ]
+        );
+        assert_eq!(todos[2].kind, "FIXME");
+
+        let todos = parse("// TODO(alice, task:fix-parser): promoted\n", path);
+        assert_eq!(todos[0].task.as_deref(), Some("fix-parser"));
+        assert_eq!(todos[0].owner.as_deref(), Some("alice"));
+
+        let todos = parse("// TODO(@alice): mentioned\n", path);
+        assert!(todos[0].is_owned_by("alice"));
+        assert!(todos[0].is_owned_by("@alice"));
+        assert!(!todos[0].is_owned_by("bob"));
+    }
+
+    #[test]
+    fn test_parse_skip_todos() {
+        let path: &Path = "/home/main.md".as_ref();
+        let body = "---\nowl:\n  skip_todos: true\n---\n# TODO: level 1\n";
+        assert!(parse(body, path).is_empty());
+
+        // code has no front matter
+        let body = "---\nowl:\n  skip_todos: true\n---\n# TODO: in a script\n";
+        assert_eq!(parse(body, "/home/run.py").len(), 1);
+    }
+
     #[test]
     fn test_parse_latex() {
         let body = r#"
@@ -268,4 +1105,93 @@ extra parameters or packages included.
         let got = parse(body, path);
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn test_parse_latex_todonotes() {
+        let body = r#"
+\begin{document}
+Some text.\todo{short note} More text. % FIXME: trailing comment
+\todo[inline, color=red]{a note which
+  spans {two} lines}
+\missingfigure{plot of the results}
+50\% done, \todototoc is not a todo
+\begin{comment}
+\todo{inside a comment environment}
+% TODO: inside a comment environment
+\end{comment}
+\begin{verbatim}
+\todo{verbatim}
+\end{verbatim}
+\end{document}
+"#;
+        let path: &Path = "/home/main.tex".as_ref();
+        let todos = parse(body, path);
+
+        let got = todos
+            .iter()
+            .map(|t| {
+                (
+                    t.line_number,
+                    t.end_line_number,
+                    t.kind.as_str(),
+                    t.title.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            got,
+            vec![
+                (3, 3, "FIXME", "trailing comment"),
+                (3, 3, "TODO", "short note"),
+                (4, 5, "TODO", "a note which spans {two} lines"),
+                (6, 6, "TODO", "missing figure: plot of the results"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_latex_verbatim_line() {
+        let body = r#"\begin{comment}\todo{hidden}\end{comment}
+% TODO: after a one line comment environment
+\todo{before} \begin{verbatim}% FIXME: hidden\end{verbatim} \todo{after} % HACK: too
+\begin{verbatim}\todo{hidden}
+\end{verbatim}\todo{after the end}
+"#;
+        let path: &Path = "/home/main.tex".as_ref();
+        let got = parse(body, path)
+            .into_iter()
+            .map(|t| (t.line_number, t.kind, t.title))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            got,
+            vec![
+                (
+                    2,
+                    "TODO".into(),
+                    "after a one line comment environment".into()
+                ),
+                (3, "HACK".into(), "too".into()),
+                (3, "TODO".into(), "before".into()),
+                (3, "TODO".into(), "after".into()),
+                (5, "TODO".into(), "after the end".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_typst_comments() {
+        let body = r#"
+= Intro
+Some text // TODO: trailing
+/* FIXME: block */
+"// TODO: in a string"
+"#;
+        let path: &Path = "/home/main.typ".as_ref();
+
+        let expected = vec![
+            Todo::new("trailing", 3, path),
+            Todo::new("block", 4, path).with_kind("FIXME"),
+        ];
+        assert_eq!(expected, parse(body, path));
+    }
 }